@@ -0,0 +1,11 @@
+# wf bash widget: pick a workflow and insert the resolved command at the cursor.
+# Load with: eval "$(wf shell-init bash)"
+_wf_widget() {
+  local cmd
+  cmd="$(wf --print --shell bash </dev/tty)"
+  if [[ -n "$cmd" ]]; then
+    READLINE_LINE="${READLINE_LINE:0:$READLINE_POINT}${cmd}${READLINE_LINE:$READLINE_POINT}"
+    READLINE_POINT=$((READLINE_POINT + ${#cmd}))
+  fi
+}
+bind -x '"{{key}}": _wf_widget'
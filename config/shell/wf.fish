@@ -0,0 +1,10 @@
+# wf fish widget: pick a workflow and insert the resolved command at the cursor.
+# Load with: wf shell-init fish | source
+function _wf_widget
+    set -l cmd (wf --print --shell fish </dev/tty | string collect)
+    if test -n "$cmd"
+        commandline -i -- $cmd
+    end
+    commandline -f repaint
+end
+bind {{key}} _wf_widget
@@ -11,7 +11,7 @@ use ractor::{Actor, ActorProcessingErr, ActorRef, RpcReplyPort, SpawnErr, rpc::C
 use tracing::{Level, event};
 
 use crate::{
-    AppContext,
+    AppContext, AppOptions,
     actor::{
         manager::WorkflowManager,
         message::{GuardianMessage, SystemHealth, WorkflowManagerMessage}
@@ -27,7 +27,11 @@ pub struct GuardianState {
     /// System startup time for uptime calculation
     startup_time:     SystemTime,
     /// System initialization flag
-    is_initialized:   bool
+    is_initialized:   bool,
+    /// Command line options used to build the AppContext
+    options:          AppOptions,
+    /// Why the AppContext couldn't be built (e.g. an unknown profile), given back to commands
+    startup_error:    Option<WorkflowError>
 }
 
 /// Guardian Actor - Root supervisor of the actor system
@@ -35,18 +39,24 @@ pub struct Guardian;
 
 #[async_trait::async_trait]
 impl Actor for Guardian {
-    type Arguments = ();
+    type Arguments = AppOptions;
     type Msg = GuardianMessage;
     type State = GuardianState;
 
     async fn pre_start(
         &self,
         _myself: ActorRef<Self::Msg>,
-        _args: Self::Arguments
+        options: Self::Arguments
     ) -> Result<Self::State, ActorProcessingErr> {
         event!(Level::DEBUG, event = guardian::GUARDIAN_STARTED);
 
-        Ok(GuardianState { workflow_manager: None, startup_time: SystemTime::now(), is_initialized: false })
+        Ok(GuardianState {
+            workflow_manager: None,
+            startup_time: SystemTime::now(),
+            is_initialized: false,
+            options,
+            startup_error: None
+        })
     }
 
     async fn handle(
@@ -68,8 +78,8 @@ impl Actor for Guardian {
 
 impl Guardian {
     /// Spawn the complete actor system
-    pub async fn spawn_system() -> Result<ActorRef<GuardianMessage>, SpawnErr> {
-        let (guardian_ref, _handle) = Actor::spawn(Some("guardian".to_string()), Guardian, ()).await?;
+    pub async fn spawn_system(options: AppOptions) -> Result<ActorRef<GuardianMessage>, SpawnErr> {
+        let (guardian_ref, _handle) = Actor::spawn(Some("guardian".to_string()), Guardian, options).await?;
 
         // Initialize the system
         if let Err(e) = guardian_ref.cast(GuardianMessage::Initialize) {
@@ -87,10 +97,19 @@ impl Guardian {
     ) -> Result<(), ActorProcessingErr> {
         event!(Level::DEBUG, event = guardian::CHILDREN_SPAWNING);
 
-        // Create AppContext
-        let app_context = Arc::new(AppContext::init().map_err(|e| {
-            ActorProcessingErr::from(t_params!("error_failed_to_create_app_context", &[&e.to_string()]))
-        })?);
+        // Create AppContext; a failure is kept so commands can report it instead of a closed channel
+        let app_context = match AppContext::init(&state.options) {
+            Ok(app_context) => Arc::new(app_context),
+            Err(e) => {
+                event!(
+                    Level::ERROR,
+                    event = guardian::CHILDREN_SPAWN_FAILED,
+                    error = %t_params!("error_failed_to_create_app_context", &[&e.to_string()])
+                );
+                state.startup_error = Some(e);
+                return Ok(());
+            }
+        };
 
         // Spawn WorkflowManager
         match Actor::spawn(Some("workflow_manager".to_string()), WorkflowManager, app_context).await {
@@ -209,7 +228,11 @@ impl Guardian {
                     }
                 }
             }
-        } else if let Err(e) = reply.send(Err(WorkflowError::Other(t!("error_actor_system_not_initialized")))) {
+        } else if let Err(e) = reply.send(Err(state
+            .startup_error
+            .clone()
+            .unwrap_or_else(|| WorkflowError::Other(t!("error_actor_system_not_initialized")))))
+        {
             event!(Level::ERROR, event = guardian::COMMAND_SUBMITTED, error = %e);
         }
 
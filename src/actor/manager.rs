@@ -146,11 +146,14 @@ impl WorkflowManager {
             }
         };
 
-        // Use call to get the actual command processing result
+        // Use call to get the actual command processing result. Commands running the workflow
+        // aren't timed out: their process would be left running while the session moves on
+        let timeout =
+            if command.runs_processes() { None } else { state.app_context.config.settings.execution.timeout() };
         match call(
             &processor_ref,
             |reply| CommandProcessorMessage::ProcessCommand { command: command.clone(), reply },
-            Some(std::time::Duration::from_secs(300))
+            timeout
         )
         .await
         {
@@ -167,7 +170,8 @@ impl WorkflowManager {
             Ok(CallResult::Timeout) => {
                 event!(Level::ERROR, event = workflow_manager::COMMAND_SUBMITTED,
                        session_id = %session_id, message = "call_timeout");
-                return Err(ActorProcessingErr::from(t_params!("error_command_timeout", &[&"5 minutes"])));
+                let seconds = timeout.map(|timeout| timeout.as_secs()).unwrap_or_default();
+                return Err(ActorProcessingErr::from(t_params!("error_command_timeout", &[&format!("{}s", seconds)])));
             }
             Ok(CallResult::SenderError) => {
                 event!(Level::ERROR, event = workflow_manager::COMMAND_SUBMITTED,
@@ -120,11 +120,26 @@ impl CommandProcessor {
     }
 
     /// Core command processing logic - clean and simple!
+    /// Repeated for as long as the command continues, e.g. once per step of a run.
     async fn process_command(
         &self,
         command: &WorkflowCommand,
         context: &EngineContext,
         state: &mut CommandProcessorState
+    ) -> Result<(), WorkflowError> {
+        loop {
+            self.process_command_once(command, context, state).await?;
+            if !command.continues(&state.current_state) {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn process_command_once(
+        &self,
+        command: &WorkflowCommand,
+        context: &EngineContext,
+        state: &mut CommandProcessorState
     ) -> Result<(), WorkflowError> {
         // 1. Load command data
         let loaded_data = command.load(context, &state.app_context, &state.current_state).await?;
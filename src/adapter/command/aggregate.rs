@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use chrono::Utc;
 use tabled::{
@@ -14,7 +16,7 @@ use crate::{
         error::{StorageError, ValidationError, WorkflowError},
         event::{AggregateReplayedEvent, WorkflowEvent},
         state::{StateDisplay, WorkflowState},
-        workflow::Workflow
+        workflow::{Workflow, WorkflowContext}
     },
     port::{command::Command, output::OutputWriter, storage::EventStore},
     t, t_params
@@ -202,7 +204,7 @@ impl Command for ReplayAggregateCommand {
         _loaded_data: &Self::LoadedData,
         _previous_state: &WorkflowState,
         _current_state: &WorkflowState,
-        _context: &EngineContext,
+        context: &EngineContext,
         app_context: &AppContext
     ) -> Result<(), Self::Error> {
         let full_id = if self.aggregate_id.contains('-') {
@@ -218,23 +220,9 @@ impl Command for ReplayAggregateCommand {
         app_context.output.step(&t!("storage_replay_state"));
         display_state(&state, &*app_context.output);
 
-        let command_parts: Option<(&Workflow, &std::collections::HashMap<String, String>)> = match &state {
-            WorkflowState::WorkflowArgumentsResolved(s) => Some((&s.selected_workflow, &s.resolved_arguments)),
-            WorkflowState::WorkflowCompleted(s) => Some((&s.completed_workflow, &s.resolved_arguments)),
-            WorkflowState::Initial(_)
-            | WorkflowState::WorkflowsDiscovered(_)
-            | WorkflowState::WorkflowsListed(_)
-            | WorkflowState::WorkflowSelected(_)
-            | WorkflowState::WorkflowStarted(_)
-            | WorkflowState::SyncRequested(_)
-            | WorkflowState::WorkflowsSynced(_)
-            | WorkflowState::LanguageSet(_) => None
-        };
-
-        if let Some((workflow, args)) = command_parts {
-            if let Ok(rendered) = super::resolve::render_command_template(&workflow.command, args) {
-                app_context.output.step(&format!("{}\n  {}", t!("storage_replay_command"), rendered));
-            }
+        let rendered = replay_commands(&state, &replay_context(&events, &context.workflow_context));
+        if !rendered.is_empty() {
+            app_context.output.step(&format!("{}\n  {}", t!("storage_replay_command"), rendered.join("\n  ")));
         }
 
         Ok(())
@@ -326,6 +314,51 @@ impl Command for DeleteAggregateCommand {
     }
 }
 
+/// Context to render a session's commands with: the one recorded with its resolved arguments,
+/// with this run's environment (which isn't recorded). `current` for sessions recorded before the
+/// context was kept.
+fn replay_context(events: &[WorkflowEvent], current: &WorkflowContext) -> WorkflowContext {
+    let recorded = events.iter().rev().find_map(|event| match event {
+        WorkflowEvent::WorkflowArgumentsResolved(resolved) => resolved.context.as_ref(),
+        _ => None
+    });
+    match recorded {
+        Some(recorded) => WorkflowContext { env: current.env.clone(), ..recorded.clone() },
+        None => current.clone()
+    }
+}
+
+/// The command, or every step's command, of a session that got as far as resolving its arguments
+fn replay_commands(state: &WorkflowState, workflow_context: &WorkflowContext) -> Vec<String> {
+    let (workflow, args): (&Workflow, &HashMap<String, String>) = match state {
+        WorkflowState::WorkflowArgumentsResolved(s) => (&s.selected_workflow, &s.resolved_arguments),
+        WorkflowState::WorkflowExecuted(s) => (&s.selected_workflow, &s.resolved_arguments),
+        WorkflowState::WorkflowExecutionFailed(s) => (&s.selected_workflow, &s.resolved_arguments),
+        WorkflowState::WorkflowStepCompleted(s) => (&s.selected_workflow, &s.resolved_arguments),
+        WorkflowState::WorkflowCompleted(s) => (&s.completed_workflow, &s.resolved_arguments),
+        WorkflowState::Initial(_)
+        | WorkflowState::WorkflowsDiscovered(_)
+        | WorkflowState::WorkflowsListed(_)
+        | WorkflowState::WorkflowSelected(_)
+        | WorkflowState::WorkflowStarted(_)
+        | WorkflowState::SyncRequested(_)
+        | WorkflowState::WorkflowsSynced(_)
+        | WorkflowState::LanguageSet(_) => return vec![]
+    };
+
+    let templates: Vec<&str> = if workflow.is_multi_step() {
+        workflow.steps.iter().map(|step| step.command.as_str()).collect()
+    } else {
+        vec![workflow.command.as_str()]
+    };
+    templates
+        .into_iter()
+        .filter_map(|template| {
+            super::resolve::render_workflow_template(workflow, template, args, workflow_context).ok()
+        })
+        .collect()
+}
+
 fn extract_workflow_name(state: &WorkflowState) -> String {
     match state {
         WorkflowState::Initial(_) => "-".to_string(),
@@ -334,6 +367,9 @@ fn extract_workflow_name(state: &WorkflowState) -> String {
         WorkflowState::WorkflowSelected(s) => s.selected_workflow.name.clone(),
         WorkflowState::WorkflowStarted(s) => s.selected_workflow.name.clone(),
         WorkflowState::WorkflowArgumentsResolved(s) => s.selected_workflow.name.clone(),
+        WorkflowState::WorkflowExecuted(s) => s.selected_workflow.name.clone(),
+        WorkflowState::WorkflowExecutionFailed(s) => s.selected_workflow.name.clone(),
+        WorkflowState::WorkflowStepCompleted(s) => s.selected_workflow.name.clone(),
         WorkflowState::WorkflowCompleted(s) => s.completed_workflow.name.clone(),
         WorkflowState::SyncRequested(_) => "(sync)".to_string(),
         WorkflowState::WorkflowsSynced(_) => "(sync)".to_string(),
@@ -374,7 +410,7 @@ mod tests {
     use crate::{
         adapter::storage::InMemoryEventStore,
         domain::{
-            event::{WorkflowDiscoveredEvent, WorkflowEvent},
+            event::{WorkflowArgumentsResolvedEvent, WorkflowDiscoveredEvent, WorkflowEvent},
             workflow::Workflow
         }
     };
@@ -388,8 +424,12 @@ mod tests {
             source_url:  None,
             author:      None,
             author_url:  None,
+            execute:     false,
+            steps:       vec![],
             shells:      vec![],
-            tags:        vec![]
+            tags:        vec![],
+            namespace:   None,
+            origin:      None
         }
     }
 
@@ -478,6 +518,35 @@ mod tests {
         assert_eq!(uuid_to_short(uuid), uuid_to_short(uuid));
     }
 
+    #[test]
+    fn replay_renders_with_the_recorded_context() {
+        let mut workflow = test_workflow();
+        workflow.command = "kubectl --context {{ env.KUBE_CONTEXT }} logs -l user={{ ctx.user }}".to_string();
+        let state = WorkflowState::WorkflowCompleted(crate::domain::state::WorkflowCompletedState {
+            discovered_workflows: vec![],
+            completed_workflow:   workflow,
+            execution_id:         "exec-1".to_string(),
+            resolved_arguments:   HashMap::new()
+        });
+
+        let mut recorded = WorkflowContext::new();
+        recorded.user = "alice".to_string();
+        recorded.env = HashMap::new();
+        let events = vec![WorkflowEvent::WorkflowArgumentsResolved(WorkflowArgumentsResolvedEvent {
+            event_id:  "e1".to_string(),
+            timestamp: chrono::Utc::now(),
+            arguments: HashMap::new(),
+            context:   Some(recorded)
+        })];
+
+        let mut current = WorkflowContext::new();
+        current.user = "bob".to_string();
+        current.env = HashMap::from([("KUBE_CONTEXT".to_string(), "prod".to_string())]);
+        let context = replay_context(&events, &current);
+        assert_eq!(replay_commands(&state, &context), vec!["kubectl --context prod logs -l user=alice"]);
+        assert_eq!(replay_context(&[], &current).user, "bob");
+    }
+
     #[test]
     fn extract_name_from_completed_state() {
         let state = WorkflowState::WorkflowCompleted(crate::domain::state::WorkflowCompletedState {
@@ -15,11 +15,15 @@ use crate::{
     t
 };
 
-/// Build a WorkflowCompletedEvent from WorkflowArgumentsResolved state.
-/// Returns error if state is not WorkflowArgumentsResolved.
+/// Build a WorkflowCompletedEvent from WorkflowArgumentsResolved, WorkflowExecuted or
+/// WorkflowStepCompleted state. Returns error for any other state, including a failed execution or
+/// step.
 pub fn build_completed_event(state: &WorkflowState) -> Result<WorkflowCompletedEvent, WorkflowError> {
     match state {
-        WorkflowState::WorkflowArgumentsResolved(_) => {
+        WorkflowState::WorkflowArgumentsResolved(_) | WorkflowState::WorkflowExecuted(_) => {
+            Ok(WorkflowCompletedEvent { event_id: Uuid::new_v4().to_string(), timestamp: Utc::now() })
+        }
+        WorkflowState::WorkflowStepCompleted(s) if !s.failed() => {
             Ok(WorkflowCompletedEvent { event_id: Uuid::new_v4().to_string(), timestamp: Utc::now() })
         }
         _ => Err(ValidationError::InvalidState(t!("error_no_workflow_ready_to_complete")).into())
@@ -94,7 +98,7 @@ mod tests {
 
     use super::*;
     use crate::domain::{
-        state::{InitialState, WorkflowArgumentsResolvedState},
+        state::{InitialState, WorkflowArgumentsResolvedState, WorkflowExecutedState},
         workflow::Workflow
     };
 
@@ -107,8 +111,12 @@ mod tests {
             source_url:  None,
             author:      None,
             author_url:  None,
+            execute:     false,
+            steps:       vec![],
             shells:      vec![],
-            tags:        vec![]
+            tags:        vec![],
+            namespace:   None,
+            origin:      None
         }
     }
 
@@ -124,6 +132,20 @@ mod tests {
         assert!(!event.event_id.is_empty());
     }
 
+    #[test]
+    fn build_event_from_executed_state() {
+        let state = WorkflowState::WorkflowExecuted(WorkflowExecutedState {
+            discovered_workflows: vec![],
+            selected_workflow:    test_workflow("deploy"),
+            execution_id:         "exec-1".to_string(),
+            resolved_arguments:   HashMap::new(),
+            command:              "echo test".to_string(),
+            exit_code:            0,
+            duration_ms:          5
+        });
+        assert!(build_completed_event(&state).is_ok());
+    }
+
     #[test]
     fn build_event_from_wrong_state_returns_error() {
         let state = WorkflowState::Initial(InitialState);
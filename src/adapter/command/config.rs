@@ -0,0 +1,289 @@
+use async_trait::async_trait;
+
+use crate::{
+    AppContext,
+    domain::{
+        command::{GetConfigCommand, ListConfigCommand, SetConfigCommand, UnsetConfigCommand},
+        engine::EngineContext,
+        error::WorkflowError,
+        event::WorkflowEvent,
+        state::WorkflowState
+    },
+    port::{command::Command, output::OutputWriter},
+    service::settings::{SETTINGS_KEYS, Settings},
+    t_params
+};
+
+/// `WF_*` variable set for `key` in this run, if any
+fn env_override(key: &str) -> Option<String> {
+    let name = Settings::env_var(key);
+    std::env::var(&name).is_ok().then_some(name)
+}
+
+/// Warn that a change to `key` won't show in this run because a `WF_*` variable overrides it
+fn warn_if_overridden(output: &dyn OutputWriter, key: &str) {
+    if let Some(name) = env_override(key) {
+        output.warning(&t_params!("config_env_override_warning", &[key, &name]));
+    }
+}
+
+/// One `key = value` line per setting, noting the variable overriding a key
+fn list_lines(settings: &Settings, env_override: impl Fn(&str) -> Option<String>) -> Vec<String> {
+    SETTINGS_KEYS
+        .iter()
+        .map(|key| {
+            let value = settings.get(key).unwrap_or_default();
+            match env_override(key) {
+                Some(name) => format!("{} = {} {}", key, value, t_params!("config_overridden_by", &[&name])),
+                None => format!("{} = {}", key, value)
+            }
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Command for GetConfigCommand {
+    type Error = WorkflowError;
+    type LoadedData = String;
+
+    async fn load(
+        &self,
+        _context: &EngineContext,
+        app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        app_context.config.settings.get(&self.key)
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        Ok(vec![])
+    }
+
+    async fn effect(
+        &self,
+        loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        _current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        app_context.output.raw(loaded_data);
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "get-config"
+    }
+
+    fn description(&self) -> &'static str {
+        "Prints the value of a setting"
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn is_mutating(&self) -> bool {
+        false
+    }
+}
+
+#[async_trait]
+impl Command for SetConfigCommand {
+    type Error = WorkflowError;
+    type LoadedData = ();
+
+    async fn load(
+        &self,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        Ok(())
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _context: &EngineContext,
+        app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        app_context.config.update_settings(|settings| settings.set(&self.key, &self.value))?;
+        Ok(vec![])
+    }
+
+    async fn effect(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        _current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        app_context.output.success(&t_params!("config_set_success", &[&self.key, &self.value]));
+        warn_if_overridden(app_context.output.as_ref(), &self.key);
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "set-config"
+    }
+
+    fn description(&self) -> &'static str {
+        "Changes a setting in config.toml"
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn is_mutating(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl Command for UnsetConfigCommand {
+    type Error = WorkflowError;
+    type LoadedData = ();
+
+    async fn load(
+        &self,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        Ok(())
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _context: &EngineContext,
+        app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        app_context.config.update_settings(|settings| settings.unset(&self.key))?;
+        Ok(vec![])
+    }
+
+    async fn effect(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        _current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        app_context.output.success(&t_params!("config_unset_success", &[&self.key]));
+        warn_if_overridden(app_context.output.as_ref(), &self.key);
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "unset-config"
+    }
+
+    fn description(&self) -> &'static str {
+        "Puts a setting back to its default"
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn is_mutating(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl Command for ListConfigCommand {
+    type Error = WorkflowError;
+    type LoadedData = Vec<String>;
+
+    async fn load(
+        &self,
+        _context: &EngineContext,
+        app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        Ok(list_lines(&app_context.config.settings, env_override))
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        Ok(vec![])
+    }
+
+    async fn effect(
+        &self,
+        loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        _current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        app_context.output.raw(&loaded_data.join("\n"));
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "list-config"
+    }
+
+    fn description(&self) -> &'static str {
+        "Lists every setting with its value"
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn is_mutating(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_shows_every_key_and_overridden_values() {
+        let lines = list_lines(&Settings::default(), |key| (key == "ui.page_size").then(|| Settings::env_var(key)));
+
+        assert_eq!(lines.len(), SETTINGS_KEYS.len());
+        assert_eq!(lines[0], "ui.language = en");
+        assert_eq!(lines[1], "ui.page_size = 10 config_overridden_by");
+        assert_eq!(lines[5], "clipboard.confirm = true");
+    }
+}
@@ -1,46 +1,56 @@
+use std::{collections::HashSet, path::PathBuf};
+
 use async_trait::async_trait;
 use uuid::Uuid;
 
 use crate::{
     AppContext,
+    adapter::loader::{find_workflow_files, inspect_workflow, namespace_of, workflow_search_path},
     domain::{
-        command::{DiscoverWorkflowsCommand, DiscoverWorkflowsData},
+        command::{DiscoverWorkflowsCommand, DiscoverWorkflowsData, DiscoveredWorkflow},
         engine::EngineContext,
         error::{StorageError, WorkflowError},
-        event::{WorkflowDiscoveredEvent, WorkflowEvent},
+        event::{WorkflowDiscoveredEvent, WorkflowDiscoveryFailedEvent, WorkflowEvent},
         state::WorkflowState,
-        workflow::Workflow
+        workflow::WorkflowOrigin
     },
     port::{command::Command, filesystem::FileSystem},
     t_params
 };
 
-/// Discover workflow YAML files from a directory using the FileSystem trait.
-/// Returns a sorted list of parsed Workflow objects.
+/// Discover workflow YAML files along the search path and in their subfolders using the FileSystem
+/// trait. Each workflow is namespaced by its folder (`k8s/logs.yaml` is in `k8s`), and `.wfignore`
+/// is respected. A workflow whose name was already found in an earlier directory of `search_path`
+/// is shadowed by it. Returns the parsed workflows sorted by name, and the files that could not be
+/// loaded. A bad file doesn't hide the others. With `strict`, files with unknown fields are bad
+/// files.
 pub fn discover_workflows(
     fs: &dyn FileSystem,
-    workflows_dir: &std::path::Path
-) -> Result<Vec<Workflow>, WorkflowError> {
-    if !fs.exists(workflows_dir) {
-        return Ok(vec![]);
-    }
-
-    let entries = fs.read_dir_entries(workflows_dir)?;
-    let mut workflows = Vec::new();
-
-    for path in entries {
-        if let Some(extension) = path.extension() {
-            if extension == "yaml" || extension == "yml" {
-                let content = fs.read_to_string(&path)?;
-                let workflow: Workflow = serde_yaml::from_str(&content)
-                    .map_err(|e| WorkflowError::from(StorageError::Serialization(e.to_string())))?;
-                workflows.push(workflow);
+    search_path: &[WorkflowOrigin],
+    strict: bool
+) -> Result<DiscoverWorkflowsData, WorkflowError> {
+    let mut workflows: Vec<DiscoveredWorkflow> = Vec::new();
+    let mut failures = Vec::new();
+
+    for origin in search_path.iter().filter(|origin| fs.exists(&origin.dir)) {
+        let shadowed: HashSet<String> = workflows.iter().map(|d| d.workflow.name.clone()).collect();
+
+        for path in find_workflow_files(fs, &origin.dir)? {
+            match inspect_workflow(fs, &path, strict) {
+                Ok(workflow) if shadowed.contains(&workflow.name) => {}
+                Ok(mut workflow) => {
+                    workflow.namespace = namespace_of(&origin.dir, &path);
+                    workflow.origin = Some(origin.clone());
+                    let file_path = path.strip_prefix(&origin.dir).unwrap_or(&path).to_string_lossy().into_owned();
+                    workflows.push(DiscoveredWorkflow { file_path, workflow });
+                }
+                Err(failure) => failures.push(failure)
             }
         }
     }
 
-    workflows.sort_by(|a, b| a.name.cmp(&b.name));
-    Ok(workflows)
+    workflows.sort_by(|a, b| a.workflow.name.cmp(&b.workflow.name).then_with(|| a.file_path.cmp(&b.file_path)));
+    Ok(DiscoverWorkflowsData { workflows, failures })
 }
 
 #[async_trait]
@@ -50,22 +60,19 @@ impl Command for DiscoverWorkflowsCommand {
 
     async fn load(
         &self,
-        _context: &EngineContext,
+        context: &EngineContext,
         app_context: &AppContext,
         _current_state: &WorkflowState
     ) -> Result<Self::LoadedData, Self::Error> {
-        let workflows_dir = app_context.config.workflows_dir.clone();
+        let config = &app_context.config;
+        let working_directory = PathBuf::from(&context.workflow_context.working_directory);
         let fs = app_context.filesystem.clone();
+        let strict = config.deny_unknown_fields;
+        let search_path = workflow_search_path(&*fs, &working_directory, &config.workflows_dir, &config.workflow_path);
 
-        let workflows =
-            tokio::task::spawn_blocking(move || discover_workflows(&*fs, &workflows_dir)).await.map_err(|e| {
-                WorkflowError::Storage(StorageError::Io(t_params!(
-                    "error_failed_to_discover_workflows",
-                    &[&e.to_string()]
-                )))
-            })??;
-
-        Ok(DiscoverWorkflowsData { workflows })
+        tokio::task::spawn_blocking(move || discover_workflows(&*fs, &search_path, strict)).await.map_err(|e| {
+            WorkflowError::Storage(StorageError::Io(t_params!("error_failed_to_discover_workflows", &[&e.to_string()])))
+        })?
     }
 
     fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
@@ -81,27 +88,38 @@ impl Command for DiscoverWorkflowsCommand {
     ) -> Result<Vec<WorkflowEvent>, Self::Error> {
         let mut events = Vec::new();
 
-        for workflow in &loaded_data.workflows {
+        for discovered in &loaded_data.workflows {
             let event = WorkflowDiscoveredEvent {
                 event_id:  Uuid::new_v4().to_string(),
                 timestamp: chrono::Utc::now(),
-                workflow:  workflow.clone(),
-                file_path: format!("{}.yaml", workflow.name)
+                workflow:  discovered.workflow.clone(),
+                file_path: discovered.file_path.clone()
             };
             events.push(WorkflowEvent::WorkflowDiscovered(event));
         }
 
+        for failure in &loaded_data.failures {
+            events.push(WorkflowEvent::WorkflowDiscoveryFailed(WorkflowDiscoveryFailedEvent {
+                event_id:  Uuid::new_v4().to_string(),
+                timestamp: chrono::Utc::now(),
+                failure:   failure.clone()
+            }));
+        }
+
         Ok(events)
     }
 
     async fn effect(
         &self,
-        _loaded_data: &Self::LoadedData,
+        loaded_data: &Self::LoadedData,
         _previous_state: &WorkflowState,
         _current_state: &WorkflowState,
         _context: &EngineContext,
-        _app_context: &AppContext
+        app_context: &AppContext
     ) -> Result<(), Self::Error> {
+        for failure in &loaded_data.failures {
+            app_context.output.warning(&t_params!("workflow_discovery_failed", &[&failure.to_string()]));
+        }
         Ok(())
     }
 
@@ -129,6 +147,10 @@ mod tests {
     use super::*;
     use crate::adapter::filesystem::mock::MockFileSystem;
 
+    fn global(dir: &Path) -> Vec<WorkflowOrigin> {
+        vec![WorkflowOrigin { dir: dir.to_path_buf(), label: "global".to_string() }]
+    }
+
     fn yaml_content(name: &str) -> String {
         format!("name: {}\ndescription: test workflow\ncommand: echo hello\narguments: []\ntags: []\nshells: []", name)
     }
@@ -141,7 +163,8 @@ mod tests {
         fs.write(&dir.join("alpha.yaml"), &yaml_content("alpha")).unwrap();
         fs.write(&dir.join("beta.yml"), &yaml_content("beta")).unwrap();
 
-        let result = discover_workflows(&fs, dir).unwrap();
+        let result = discover_workflows(&fs, &global(dir), false).unwrap().workflows;
+        let result: Vec<_> = result.into_iter().map(|discovered| discovered.workflow).collect();
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].name, "alpha");
         assert_eq!(result[1].name, "beta");
@@ -156,7 +179,8 @@ mod tests {
         fs.write(&dir.join("wf.yaml"), &yaml_content("wf")).unwrap();
         fs.write(&dir.join("data.json"), "{}").unwrap();
 
-        let result = discover_workflows(&fs, dir).unwrap();
+        let result = discover_workflows(&fs, &global(dir), false).unwrap().workflows;
+        let result: Vec<_> = result.into_iter().map(|discovered| discovered.workflow).collect();
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].name, "wf");
     }
@@ -167,26 +191,60 @@ mod tests {
         let dir = Path::new("/workflows");
         fs.create_dir_all(dir).unwrap();
 
-        let result = discover_workflows(&fs, dir).unwrap();
+        let result = discover_workflows(&fs, &global(dir), false).unwrap().workflows;
+        let result: Vec<_> = result.into_iter().map(|discovered| discovered.workflow).collect();
         assert!(result.is_empty());
     }
 
     #[test]
     fn nonexistent_directory_returns_empty_vec() {
         let fs = MockFileSystem::new();
-        let result = discover_workflows(&fs, Path::new("/nope")).unwrap();
+        let result = discover_workflows(&fs, &global(Path::new("/nope")), false).unwrap().workflows;
+        let result: Vec<_> = result.into_iter().map(|discovered| discovered.workflow).collect();
         assert!(result.is_empty());
     }
 
     #[test]
-    fn invalid_yaml_returns_error() {
+    fn invalid_yaml_is_reported_with_its_position() {
+        let fs = MockFileSystem::new();
+        let dir = Path::new("/workflows");
+        fs.create_dir_all(dir).unwrap();
+        fs.write(&dir.join("good.yaml"), &yaml_content("good")).unwrap();
+        fs.write(&dir.join("bad.yaml"), "name: bad\ndescription: [broken\ncommand: echo").unwrap();
+
+        let result = discover_workflows(&fs, &global(dir), false).unwrap();
+        assert_eq!(result.workflows.len(), 1);
+        assert_eq!(result.workflows[0].workflow.name, "good");
+
+        assert_eq!(result.failures.len(), 1);
+        let failure = &result.failures[0];
+        assert_eq!(failure.path, "/workflows/bad.yaml");
+        assert!(failure.line.is_some() && failure.column.is_some());
+    }
+
+    #[test]
+    fn invalid_workflow_is_located_in_its_file() {
         let fs = MockFileSystem::new();
         let dir = Path::new("/workflows");
         fs.create_dir_all(dir).unwrap();
-        fs.write(&dir.join("bad.yaml"), "not: [valid: workflow").unwrap();
+        fs.write(&dir.join("bad.yaml"), "name: bad\ndescription: test\ncommand: echo\nexecute: maybe").unwrap();
 
-        let result = discover_workflows(&fs, dir);
-        assert!(result.is_err());
+        let result = discover_workflows(&fs, &global(dir), false).unwrap();
+        assert!(result.workflows.is_empty());
+        assert_eq!(result.failures[0].line, Some(4));
+        assert!(!result.failures[0].message.contains("line 4"));
+    }
+
+    #[test]
+    fn broken_fragment_is_blamed_on_the_fragment() {
+        let fs = MockFileSystem::new();
+        let dir = Path::new("/workflows");
+        fs.write(&dir.join("_arguments/env.yaml"), "description: [broken").unwrap();
+        fs.write(&dir.join("deploy.yaml"), &format!("{}\ninclude_arguments: [env]", yaml_content("deploy"))).unwrap();
+
+        let result = discover_workflows(&fs, &global(dir), false).unwrap();
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].path, "/workflows/_arguments/env.yaml");
     }
 
     #[test]
@@ -198,9 +256,85 @@ mod tests {
         fs.write(&dir.join("alpha.yaml"), &yaml_content("alpha")).unwrap();
         fs.write(&dir.join("middle.yaml"), &yaml_content("middle")).unwrap();
 
-        let result = discover_workflows(&fs, dir).unwrap();
+        let result = discover_workflows(&fs, &global(dir), false).unwrap().workflows;
+        let result: Vec<_> = result.into_iter().map(|discovered| discovered.workflow).collect();
         assert_eq!(result[0].name, "alpha");
         assert_eq!(result[1].name, "middle");
         assert_eq!(result[2].name, "zebra");
     }
+
+    #[test]
+    fn subfolders_are_namespaces() {
+        let fs = MockFileSystem::new();
+        let dir = Path::new("/workflows");
+        fs.write(&dir.join("top.yaml"), &yaml_content("top")).unwrap();
+        fs.write(&dir.join("k8s/logs.yaml"), &yaml_content("logs")).unwrap();
+        fs.write(&dir.join("aws/s3/sync.yml"), &yaml_content("sync")).unwrap();
+        fs.write(&dir.join("k8s/_arguments/namespace.yaml"), "description: Namespace").unwrap();
+        fs.write(&dir.join(".git/config.yaml"), "not: a workflow").unwrap();
+
+        let result = discover_workflows(&fs, &global(dir), false).unwrap();
+        assert!(result.failures.is_empty());
+        let found: Vec<(&str, Option<&str>, &str)> = result
+            .workflows
+            .iter()
+            .map(|d| (d.workflow.name.as_str(), d.workflow.namespace.as_deref(), d.file_path.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("logs", Some("k8s"), "k8s/logs.yaml"),
+                ("sync", Some("aws/s3"), "aws/s3/sync.yml"),
+                ("top", None, "top.yaml")
+            ]
+        );
+    }
+
+    #[test]
+    fn wfignore_hides_files_and_folders() {
+        let fs = MockFileSystem::new();
+        let dir = Path::new("/workflows");
+        fs.write(&dir.join(".wfignore"), "drafts/\n*.wip.yaml").unwrap();
+        fs.write(&dir.join("keep.yaml"), &yaml_content("keep")).unwrap();
+        fs.write(&dir.join("k8s/logs.wip.yaml"), "broken: [").unwrap();
+        fs.write(&dir.join("drafts/idea.yaml"), "broken: [").unwrap();
+
+        let result = discover_workflows(&fs, &global(dir), false).unwrap();
+        assert!(result.failures.is_empty());
+        assert_eq!(result.workflows.len(), 1);
+        assert_eq!(result.workflows[0].file_path, "keep.yaml");
+    }
+
+    #[test]
+    fn local_workflows_shadow_global_ones() {
+        let fs = MockFileSystem::new();
+        fs.write(Path::new("/repo/.wf/deploy.yaml"), &yaml_content("deploy")).unwrap();
+        fs.write(Path::new("/.workflows/build.yaml"), &yaml_content("build")).unwrap();
+        fs.write(Path::new("/global/deploy.yaml"), &yaml_content("deploy")).unwrap();
+        fs.write(Path::new("/global/k8s/deploy.yaml"), &yaml_content("deploy")).unwrap();
+        fs.write(Path::new("/global/logs.yaml"), &yaml_content("logs")).unwrap();
+        fs.write(Path::new("/extra/lint.yaml"), &yaml_content("lint")).unwrap();
+
+        let search_path =
+            workflow_search_path(&fs, Path::new("/repo/src"), Path::new("/global"), &[PathBuf::from("/extra")]);
+        let labels: Vec<&str> = search_path.iter().map(|origin| origin.label.as_str()).collect();
+        assert_eq!(labels, vec!["../.wf", "../../.workflows", "workflow_origin_global", "/extra"]);
+
+        let result = discover_workflows(&fs, &search_path, false).unwrap();
+        let found: Vec<(&str, &str)> = result
+            .workflows
+            .iter()
+            .map(|d| (d.workflow.name.as_str(), d.workflow.origin.as_ref().unwrap().label.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("build", "../../.workflows"),
+                ("deploy", "../.wf"),
+                ("lint", "/extra"),
+                ("logs", "workflow_origin_global")
+            ]
+        );
+        assert_eq!(result.workflows[1].workflow.directory(), Some(PathBuf::from("/repo/.wf")));
+    }
 }
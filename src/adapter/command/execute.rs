@@ -0,0 +1,205 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::resolve::render_workflow_command;
+use crate::{
+    AppContext,
+    domain::{
+        command::{ExecuteWorkflowCommand, ExecuteWorkflowData, ExecutionOutcome},
+        engine::EngineContext,
+        error::{ValidationError, WorkflowError},
+        event::{WorkflowEvent, WorkflowExecutedEvent, WorkflowExecutionFailedEvent},
+        state::WorkflowState
+    },
+    port::{command::Command, executor::CommandExecutor},
+    t, t_params
+};
+
+/// Run `command` attached to the terminal and time it.
+/// A process that cannot be started is reported as an outcome without exit code, not as an error.
+pub async fn run_command(executor: &dyn CommandExecutor, command: &str) -> ExecutionOutcome {
+    let started = Instant::now();
+    let result = executor.run_attached(command).await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(exit_code) => ExecutionOutcome { exit_code, duration_ms, error: None },
+        Err(e) => ExecutionOutcome { exit_code: None, duration_ms, error: Some(e.to_string()) }
+    }
+}
+
+/// The event recording how the run of `command` (secrets redacted) went
+pub fn build_outcome_event(command: &str, outcome: &ExecutionOutcome) -> WorkflowEvent {
+    match outcome.exit_code {
+        Some(exit_code) if outcome.succeeded() => WorkflowEvent::WorkflowExecuted(WorkflowExecutedEvent {
+            event_id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            command: command.to_string(),
+            exit_code,
+            duration_ms: outcome.duration_ms
+        }),
+        _ => WorkflowEvent::WorkflowExecutionFailed(WorkflowExecutionFailedEvent {
+            event_id:    Uuid::new_v4().to_string(),
+            timestamp:   Utc::now(),
+            command:     command.to_string(),
+            exit_code:   outcome.exit_code,
+            duration_ms: outcome.duration_ms,
+            error:       outcome.error.clone()
+        })
+    }
+}
+
+/// Explain why a run failed: the start error, the exit code or the signal
+pub fn failure_message(exit_code: Option<i32>, duration_ms: u64, error: Option<&str>) -> String {
+    match (error, exit_code) {
+        (Some(error), _) => error.to_string(),
+        (None, Some(exit_code)) => {
+            t_params!("error_workflow_execution_failed", &[&exit_code.to_string(), &duration_ms.to_string()])
+        }
+        (None, None) => t_params!("error_workflow_killed_by_signal", &[&duration_ms.to_string()])
+    }
+}
+
+#[async_trait]
+impl Command for ExecuteWorkflowCommand {
+    type Error = WorkflowError;
+    type LoadedData = ExecuteWorkflowData;
+
+    /// The process runs here rather than in `effect` so its outcome can be emitted
+    /// as this command's event.
+    async fn load(
+        &self,
+        context: &EngineContext,
+        app_context: &AppContext,
+        current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        let WorkflowState::WorkflowArgumentsResolved(state) = current_state else {
+            return Err(ValidationError::InvalidState(t!("error_no_arguments_resolved_to_execute")).into());
+        };
+
+        // Multi-step workflows run through `RunWorkflowStepsCommand`
+        if state.selected_workflow.is_multi_step() {
+            return Ok(ExecuteWorkflowData { command: String::new(), outcome: None });
+        }
+
+        // Recorded with secrets redacted, run with their real values
+        let workflow = &state.selected_workflow;
+        let command = render_workflow_command(workflow, &state.resolved_arguments, &context.workflow_context)?;
+        if !(self.forced || workflow.execute) {
+            return Ok(ExecuteWorkflowData { command, outcome: None });
+        }
+
+        let arguments = app_context.secrets.reveal(&state.execution_id, &state.resolved_arguments);
+        let runnable = render_workflow_command(workflow, &arguments, &context.workflow_context)?;
+        let outcome = run_command(&*app_context.executor, &runnable).await;
+        Ok(ExecuteWorkflowData { command, outcome: Some(outcome) })
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        loaded_data: &Self::LoadedData,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        Ok(loaded_data.outcome.iter().map(|outcome| build_outcome_event(&loaded_data.command, outcome)).collect())
+    }
+
+    async fn effect(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        match current_state {
+            WorkflowState::WorkflowExecuted(state) => {
+                app_context.output.success(&t_params!(
+                    "workflow_executed",
+                    &[&state.exit_code.to_string(), &state.duration_ms.to_string()]
+                ));
+                Ok(())
+            }
+            WorkflowState::WorkflowExecutionFailed(state) => Err(WorkflowError::Execution(failure_message(
+                state.exit_code,
+                state.duration_ms,
+                state.error.as_deref()
+            ))),
+            // Not an executing workflow: nothing ran
+            _ => Ok(())
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "execute-workflow"
+    }
+
+    fn description(&self) -> &'static str {
+        "Runs the rendered command in the user's shell and records the outcome"
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn is_mutating(&self) -> bool {
+        true
+    }
+
+    fn runs_processes(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::adapter::executor::{ShellExecutor, mock::MockExecutor};
+
+    #[tokio::test]
+    async fn successful_run_reports_exit_code_zero() {
+        let executor = MockExecutor::new(HashMap::from([("echo hi".to_string(), Ok(String::new()))]));
+        let outcome = run_command(&executor, "echo hi").await;
+        assert_eq!(outcome.exit_code, Some(0));
+        assert!(outcome.succeeded());
+        assert!(outcome.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn non_zero_exit_is_not_a_success() {
+        let executor =
+            MockExecutor::new(HashMap::from([("false".to_string(), Err(WorkflowError::Execution("x".to_string())))]));
+        let outcome = run_command(&executor, "false").await;
+        assert_eq!(outcome.exit_code, Some(1));
+        assert!(!outcome.succeeded());
+    }
+
+    #[tokio::test]
+    async fn real_exit_code_is_recorded_as_failure() {
+        let outcome = run_command(&ShellExecutor::new(), "exit 3").await;
+        assert_eq!(outcome.exit_code, Some(3));
+
+        let WorkflowEvent::WorkflowExecutionFailed(event) = build_outcome_event("deploy", &outcome) else {
+            panic!("expected WorkflowExecutionFailed")
+        };
+        assert_eq!((event.command.as_str(), event.exit_code, event.error), ("deploy", Some(3), None));
+    }
+
+    #[tokio::test]
+    async fn spawn_failure_is_recorded_as_outcome() {
+        let executor = MockExecutor::new(HashMap::new());
+        let outcome = run_command(&executor, "missing").await;
+        assert_eq!(outcome.exit_code, None);
+        assert!(outcome.error.is_some());
+    }
+}
@@ -0,0 +1,151 @@
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    AppContext,
+    adapter::loader::load_workflow,
+    domain::{
+        command::{LoadWorkflowFromFileCommand, LoadWorkflowFromFileData},
+        engine::EngineContext,
+        error::{StorageError, WorkflowError},
+        event::{WorkflowEvent, WorkflowSelectedEvent},
+        state::WorkflowState,
+        workflow::WorkflowOrigin
+    },
+    port::command::Command,
+    t, t_params
+};
+
+/// Resolve a workflow file path given on the command line.
+/// Relative paths are taken from the directory wf was invoked in.
+pub fn resolve_workflow_path(path: &str, working_directory: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() { path.to_path_buf() } else { Path::new(working_directory).join(path) }
+}
+
+/// A workflow loaded by path belongs to the folder of its file, so `relative_to: workflow`
+/// arguments resolve next to it rather than in the workflows directory
+pub fn file_origin(path: &Path) -> WorkflowOrigin {
+    let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    WorkflowOrigin { label: dir.display().to_string(), dir }
+}
+
+#[async_trait]
+impl Command for LoadWorkflowFromFileCommand {
+    type Error = WorkflowError;
+    type LoadedData = LoadWorkflowFromFileData;
+
+    async fn load(
+        &self,
+        context: &EngineContext,
+        app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        let path = resolve_workflow_path(&self.path, &context.workflow_context.working_directory);
+        let fs = app_context.filesystem.clone();
+        let strict = app_context.config.deny_unknown_fields;
+
+        let origin = file_origin(&path);
+        let mut workflow =
+            tokio::task::spawn_blocking(move || load_workflow(&*fs, &path, strict)).await.map_err(|e| {
+                WorkflowError::Storage(StorageError::Io(t_params!(
+                    "error_failed_to_load_workflow_file",
+                    &[&e.to_string()]
+                )))
+            })??;
+        workflow.origin = Some(origin);
+
+        Ok(LoadWorkflowFromFileData { workflow })
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        loaded_data: &Self::LoadedData,
+        context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        let event = WorkflowSelectedEvent {
+            event_id:  Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            workflow:  loaded_data.workflow.clone(),
+            user:      context.workflow_context.user.clone()
+        };
+
+        Ok(vec![WorkflowEvent::WorkflowSelected(event)])
+    }
+
+    async fn effect(
+        &self,
+        loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        if let WorkflowState::WorkflowSelected(_) = current_state {
+            app_context.output.step(&t_params!("workflow_loaded_from_file", &[&loaded_data.workflow.name, &self.path]));
+        } else {
+            app_context.output.warning(&t!("error_no_workflow_selected"));
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "load-workflow-from-file"
+    }
+
+    fn description(&self) -> &'static str {
+        "Loads a workflow directly from a YAML file"
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn is_mutating(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{adapter::filesystem::mock::MockFileSystem, port::filesystem::FileSystem};
+
+    #[test]
+    fn relative_path_is_joined_to_working_directory() {
+        let path = resolve_workflow_path("ops/deploy.yaml", "/repo");
+        assert_eq!(path, PathBuf::from("/repo/ops/deploy.yaml"));
+    }
+
+    #[test]
+    fn absolute_path_is_kept() {
+        let path = resolve_workflow_path("/etc/wf/deploy.yaml", "/repo");
+        assert_eq!(path, PathBuf::from("/etc/wf/deploy.yaml"));
+    }
+
+    #[test]
+    fn workflow_from_file_lives_in_the_file_folder() {
+        let fs = MockFileSystem::new();
+        let path = resolve_workflow_path("ops/deploy.yaml", "/repo");
+        fs.write(
+            &path,
+            "name: deploy\ndescription: Deploy\ncommand: kubectl apply -f manifest.yaml\narguments: []\ntags: \
+             []\nshells: []"
+        )
+        .unwrap();
+
+        let mut workflow = load_workflow(&fs, &path, false).unwrap();
+        workflow.origin = Some(file_origin(&path));
+
+        assert_eq!(workflow.directory(), Some(PathBuf::from("/repo/ops")));
+    }
+}
@@ -0,0 +1,174 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+
+use super::file::resolve_workflow_path;
+use crate::{
+    AppContext,
+    adapter::{lint::lint_paths, loader::workflow_search_path},
+    domain::{
+        command::LintWorkflowsCommand,
+        engine::EngineContext,
+        error::{StorageError, ValidationError, WorkflowError},
+        event::WorkflowEvent,
+        lint::LintReport,
+        state::WorkflowState
+    },
+    port::{command::Command, output::OutputWriter},
+    t_params
+};
+
+/// Print the findings one per line (or as JSON) followed by a summary
+pub fn print_report(report: &LintReport, json: bool, output: &dyn OutputWriter) -> Result<(), WorkflowError> {
+    if json {
+        let rendered = serde_json::to_string_pretty(report)
+            .map_err(|e| WorkflowError::Storage(StorageError::Serialization(e.to_string())))?;
+        output.raw(&rendered);
+    } else {
+        for issue in &report.issues {
+            output.raw(&issue.to_string());
+        }
+    }
+
+    let files = report.files.to_string();
+    if report.issues.is_empty() {
+        output.success(&t_params!("lint_clean", &[&files]));
+    } else {
+        output.warning(&t_params!(
+            "lint_summary",
+            &[&files, &report.errors().to_string(), &report.warnings().to_string()]
+        ));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl Command for LintWorkflowsCommand {
+    type Error = WorkflowError;
+    type LoadedData = LintReport;
+
+    async fn load(
+        &self,
+        context: &EngineContext,
+        app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        let paths: Vec<PathBuf> = if self.paths.is_empty() {
+            let config = &app_context.config;
+            let working_directory = PathBuf::from(&context.workflow_context.working_directory);
+            workflow_search_path(
+                &*app_context.filesystem,
+                &working_directory,
+                &config.workflows_dir,
+                &config.workflow_path
+            )
+            .into_iter()
+            .map(|origin| origin.dir)
+            .collect()
+        } else {
+            self.paths
+                .iter()
+                .map(|path| resolve_workflow_path(path, &context.workflow_context.working_directory))
+                .collect()
+        };
+        let fs = app_context.filesystem.clone();
+
+        tokio::task::spawn_blocking(move || lint_paths(&*fs, &paths)).await.map_err(|e| {
+            WorkflowError::Storage(StorageError::Io(t_params!("error_failed_to_lint_workflows", &[&e.to_string()])))
+        })?
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        Ok(vec![])
+    }
+
+    async fn effect(
+        &self,
+        loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        _current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        print_report(loaded_data, self.json, &*app_context.output)?;
+
+        if loaded_data.failed(self.strict) {
+            return Err(ValidationError::Other(t_params!(
+                "error_lint_failed",
+                &[&loaded_data.errors().to_string(), &loaded_data.warnings().to_string()]
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "lint-workflows"
+    }
+
+    fn description(&self) -> &'static str {
+        "Checks workflow files for mistakes without running them"
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn is_mutating(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        adapter::output::mock::MockOutput,
+        domain::lint::{LintIssue, LintSeverity}
+    };
+
+    fn report() -> LintReport {
+        LintReport {
+            files:  2,
+            issues: vec![LintIssue {
+                path:     "/w/deploy.yaml".to_string(),
+                line:     None,
+                column:   None,
+                workflow: Some("deploy".to_string()),
+                argument: Some("region".to_string()),
+                severity: LintSeverity::Warning,
+                code:     "unused-argument".to_string(),
+                message:  "Argument 'region' is never used".to_string()
+            }]
+        }
+    }
+
+    #[test]
+    fn human_output_lists_one_issue_per_line() {
+        let output = MockOutput::default();
+        print_report(&report(), false, &output).unwrap();
+        let messages = output.messages();
+        assert_eq!(messages[0], "/w/deploy.yaml: warning[unused-argument]: Argument 'region' is never used");
+        assert_eq!(messages.len(), 2);
+    }
+
+    #[test]
+    fn json_output_is_machine_readable() {
+        let output = MockOutput::default();
+        print_report(&report(), true, &output).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output.messages()[0]).unwrap();
+        assert_eq!(parsed["files"], 2);
+        assert_eq!(parsed["issues"][0]["severity"], "warning");
+        assert_eq!(parsed["issues"][0]["code"], "unused-argument");
+    }
+}
@@ -7,21 +7,31 @@ use crate::{
         state::WorkflowState
     },
     port::command::Command,
-    t, t_params
+    t
 };
 
 pub mod aggregate;
 pub mod complete;
+pub mod config;
 pub mod discover;
+pub mod execute;
+pub mod file;
 pub mod language;
+pub mod lint;
 pub mod list;
 pub mod purge;
+pub mod remote;
 pub mod resolve;
+pub mod schema;
 pub mod select;
+pub mod select_by_name;
+pub mod shell_init;
 pub mod start;
+pub mod steps;
 pub mod storage;
 pub mod sync;
 pub mod sync_record;
+pub mod template;
 
 /// Macro to implement Command trait for WorkflowCommand enum
 /// Similar to the impl_event macro for WorkflowEvent
@@ -129,6 +139,22 @@ macro_rules! impl_command {
                     )*
                 }
             }
+
+            fn runs_processes(&self) -> bool {
+                match self {
+                    $(
+                        $enum_name::$variant($field) => $field.runs_processes(),
+                    )*
+                }
+            }
+
+            fn continues(&self, state: &WorkflowState) -> bool {
+                match self {
+                    $(
+                        $enum_name::$variant($field) => $field.continues(state),
+                    )*
+                }
+            }
         }
     };
 }
@@ -137,11 +163,18 @@ impl_command!(WorkflowCommand {
     DiscoverWorkflows(cmd),
     ListWorkflows(cmd),
     InteractivelySelectWorkflow(cmd),
+    SelectWorkflowByName(cmd),
+    LoadWorkflowFromFile(cmd),
     StartWorkflow(cmd),
     CompleteWorkflow(cmd),
     ResolveArguments(cmd),
+    ExecuteWorkflow(cmd),
+    RunWorkflowSteps(cmd),
     SyncWorkflows(cmd),
     RecordSyncResult(cmd),
+    AddRemote(cmd),
+    ListRemotes(cmd),
+    RemoveRemote(cmd),
     SetLanguage(cmd),
     GetCurrentLanguage(cmd),
     ListLanguages(cmd),
@@ -150,15 +183,12 @@ impl_command!(WorkflowCommand {
     ListAggregates(cmd),
     ReplayAggregate(cmd),
     DeleteAggregate(cmd),
-    PurgeStorage(cmd)
+    PurgeStorage(cmd),
+    GetConfig(cmd),
+    SetConfig(cmd),
+    UnsetConfig(cmd),
+    ListConfig(cmd),
+    LintWorkflows(cmd),
+    PrintSchema(cmd),
+    ShellInit(cmd)
 });
-
-/// Helper function to copy text to clipboard
-fn copy_to_clipboard(text: &str) -> Result<(), WorkflowError> {
-    let mut clipboard = arboard::Clipboard::new()
-        .map_err(|e| WorkflowError::Other(t_params!("error_failed_to_create_clipboard_context", &[&e.to_string()])))?;
-    clipboard
-        .set_text(text.to_owned())
-        .map_err(|e| WorkflowError::Other(t_params!("error_failed_to_set_clipboard_contents", &[&e.to_string()])))?;
-    Ok(())
-}
@@ -0,0 +1,336 @@
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf}
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    AppContext,
+    domain::{
+        command::{AddRemoteCommand, ListRemotesCommand, RemoveRemoteCommand},
+        engine::EngineContext,
+        error::WorkflowError,
+        event::WorkflowEvent,
+        state::WorkflowState
+    },
+    port::{command::Command, filesystem::FileSystem},
+    service::settings::Remote,
+    t, t_params
+};
+
+/// File `wf sync` leaves in a remote's folder, marking the folder as safe to replace or remove
+pub const SYNC_MARKER: &str = ".wf-remote";
+
+/// A remote can take over its folder when the folder doesn't exist yet or was created by `wf sync`
+pub fn folder_owned_by_sync(filesystem: &dyn FileSystem, folder: &Path) -> bool {
+    !filesystem.exists(folder) || filesystem.exists(&folder.join(SYNC_MARKER))
+}
+
+/// Error for a folder in the way of a remote, holding workflows `wf sync` didn't put there
+pub fn folder_not_synced(folder: &Path) -> WorkflowError {
+    WorkflowError::Config(t_params!("error_remote_folder_not_synced", &[&folder.display().to_string()]))
+}
+
+/// Error for a remote that isn't configured, naming the ones that are
+pub fn unknown_remote(name: &str, remotes: &BTreeMap<String, Remote>) -> WorkflowError {
+    let configured = remotes.keys().map(String::as_str).collect::<Vec<_>>().join(", ");
+    WorkflowError::Config(t_params!("error_unknown_remote", &[name, &configured]))
+}
+
+/// Remote names become folders and namespaces, so they stick to letters, digits, `-` and `_`,
+/// and can't start with `_` (discovery skips such folders)
+pub fn validate_remote_name(name: &str) -> Result<(), WorkflowError> {
+    let valid = !name.is_empty()
+        && !name.starts_with('_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid { Ok(()) } else { Err(WorkflowError::Config(t_params!("error_invalid_remote_name", &[name]))) }
+}
+
+/// A remote's `path` names a folder inside its repository, so it must be relative and can't
+/// climb out with `..`
+pub fn validate_remote_path(path: &str) -> Result<(), WorkflowError> {
+    let inside =
+        Path::new(path).components().all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if inside { Ok(()) } else { Err(WorkflowError::Config(t_params!("error_invalid_remote_path", &[path]))) }
+}
+
+/// One line per remote: its name, URL, branch and the folder it takes workflows from
+fn remote_lines(remotes: &BTreeMap<String, Remote>) -> Vec<String> {
+    let width = remotes.keys().map(String::len).max().unwrap_or_default();
+    remotes
+        .iter()
+        .map(|(name, remote)| {
+            let mut line = format!("{:width$}  {} ({})", name, remote.url, remote.branch);
+            if let Some(path) = &remote.path {
+                line.push_str(&format!(" {}/", path));
+            }
+            line
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Command for AddRemoteCommand {
+    type Error = WorkflowError;
+    /// Folder of the same name that `wf sync` didn't create, if any
+    type LoadedData = Option<PathBuf>;
+
+    async fn load(
+        &self,
+        _context: &EngineContext,
+        app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        let folder = app_context.config.workflows_dir.join(&self.name);
+        Ok((!folder_owned_by_sync(app_context.filesystem.as_ref(), &folder)).then_some(folder))
+    }
+
+    fn validate(&self, loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        validate_remote_name(&self.name)?;
+        if let Some(path) = &self.path {
+            validate_remote_path(path)?;
+        }
+        match loaded_data {
+            Some(folder) => Err(folder_not_synced(folder)),
+            None => Ok(())
+        }
+    }
+
+    async fn emit(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        Ok(vec![])
+    }
+
+    async fn effect(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        _current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        let remote = Remote {
+            url:     self.url.clone(),
+            branch:  self.branch.clone(),
+            ssh_key: self.ssh_key.clone(),
+            path:    self.path.clone()
+        };
+        app_context.config.update_settings(|settings| {
+            let remotes = settings.remotes_mut(app_context.config.profile.as_deref())?;
+            if remotes.contains_key(&self.name) {
+                return Err(WorkflowError::Config(t_params!("error_remote_exists", &[&self.name])));
+            }
+            remotes.insert(self.name.clone(), remote);
+            Ok(())
+        })?;
+        app_context.output.success(&t_params!("remote_added", &[&self.name]));
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "add-remote"
+    }
+
+    fn description(&self) -> &'static str {
+        "Adds a remote to sync workflows from"
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn is_mutating(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl Command for ListRemotesCommand {
+    type Error = WorkflowError;
+    type LoadedData = Vec<String>;
+
+    async fn load(
+        &self,
+        _context: &EngineContext,
+        app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        Ok(remote_lines(&app_context.config.settings.remotes))
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        Ok(vec![])
+    }
+
+    async fn effect(
+        &self,
+        loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        _current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        if loaded_data.is_empty() {
+            app_context.output.info(&t!("error_no_remotes"));
+        } else {
+            app_context.output.raw(&loaded_data.join("\n"));
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "list-remotes"
+    }
+
+    fn description(&self) -> &'static str {
+        "Lists the configured remotes"
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn is_mutating(&self) -> bool {
+        false
+    }
+}
+
+#[async_trait]
+impl Command for RemoveRemoteCommand {
+    type Error = WorkflowError;
+    type LoadedData = ();
+
+    async fn load(
+        &self,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        Ok(())
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        Ok(vec![])
+    }
+
+    async fn effect(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        _current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        app_context.config.update_settings(|settings| {
+            let remotes = settings.remotes_mut(app_context.config.profile.as_deref())?;
+            match remotes.remove(&self.name) {
+                Some(_) => Ok(()),
+                None => Err(unknown_remote(&self.name, remotes))
+            }
+        })?;
+
+        let folder = app_context.config.workflows_dir.join(&self.name);
+        if !app_context.filesystem.exists(&folder) {
+            app_context.output.success(&t_params!("remote_removed", &[&self.name]));
+        } else if folder_owned_by_sync(app_context.filesystem.as_ref(), &folder) {
+            app_context.filesystem.remove_dir_all(&folder)?;
+            app_context.output.success(&t_params!("remote_removed", &[&self.name]));
+        } else {
+            app_context
+                .output
+                .warning(&t_params!("remote_removed_folder_kept", &[&self.name, &folder.display().to_string()]));
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "remove-remote"
+    }
+
+    fn description(&self) -> &'static str {
+        "Removes a remote and the workflows synced from it"
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn is_mutating(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::filesystem::mock::MockFileSystem;
+
+    #[test]
+    fn remote_names_must_work_as_folders() {
+        assert!(validate_remote_name("team-ops_2").is_ok());
+        for name in ["", "_shared", ".git", "a/b", "with space"] {
+            assert!(validate_remote_name(name).is_err(), "{} should be rejected", name);
+        }
+    }
+
+    #[test]
+    fn remote_paths_stay_inside_the_repository() {
+        for path in ["workflows", "ops/k8s", "./ops"] {
+            assert!(validate_remote_path(path).is_ok(), "{} should be accepted", path);
+        }
+        for path in ["/etc", "../other", "ops/../../home"] {
+            assert!(validate_remote_path(path).is_err(), "{} should be rejected", path);
+        }
+    }
+
+    #[test]
+    fn only_folders_created_by_sync_belong_to_a_remote() {
+        let fs = MockFileSystem::new();
+        fs.write(Path::new("/w/k8s/deploy.yaml"), "name: deploy").unwrap();
+        fs.write(Path::new("/w/ops/deploy.yaml"), "name: deploy").unwrap();
+        fs.write(&Path::new("/w/ops").join(SYNC_MARKER), "git@example.com:ops.git").unwrap();
+
+        assert!(!folder_owned_by_sync(&fs, Path::new("/w/k8s")));
+        assert!(folder_owned_by_sync(&fs, Path::new("/w/ops")));
+        assert!(folder_owned_by_sync(&fs, Path::new("/w/vault")));
+    }
+
+    #[test]
+    fn list_aligns_names_and_shows_paths() {
+        let mut vault = Remote::new("https://example.com/vault.git");
+        vault.path = Some("k8s".to_string());
+        let remotes =
+            BTreeMap::from([("ops".to_string(), Remote::new("git@example.com:ops.git")), ("vault".to_string(), vault)]);
+
+        assert_eq!(
+            remote_lines(&remotes),
+            vec!["ops    git@example.com:ops.git (main)", "vault  https://example.com/vault.git (main) k8s/"]
+        );
+    }
+}
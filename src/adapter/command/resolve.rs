@@ -1,56 +1,125 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf
+};
 
 use async_trait::async_trait;
 use chrono::Utc;
 use uuid::Uuid;
 
+use super::template::{quote_placeholders, render_template};
 use crate::{
     AppContext,
-    adapter::resolver::ArgumentResolver,
+    adapter::{
+        command::template::TeraInspector,
+        resolver::{ArgumentResolver, ResolveOptions},
+        secret::recorded_context
+    },
     domain::{
         command::{ResolveArgumentsCommand, ResolveArgumentsData},
         engine::EngineContext,
         error::{ValidationError, WorkflowError},
         event::{WorkflowArgumentsResolvedEvent, WorkflowEvent},
+        shell::ShellDialect,
         state::WorkflowState,
-        workflow::WorkflowArgument
+        workflow::{RESERVED_ARGUMENT_NAMES, Workflow, WorkflowArgument, WorkflowContext, resolution_order}
     },
     port::command::Command,
     t, t_params
 };
 
 /// Validate that all workflow arguments have been resolved.
-/// Returns Ok(()) if every argument name has a corresponding entry in resolved_arguments.
+/// Returns Ok(()) if every argument name has a corresponding entry in resolved_arguments,
+/// except for arguments skipped because their `when` condition is false for the values resolved
+/// before them.
 pub fn validate_all_resolved(
     arguments: &[WorkflowArgument],
     resolved: &HashMap<String, String>
 ) -> Result<(), WorkflowError> {
-    for arg in arguments {
-        if !resolved.contains_key(&arg.name) {
+    let order = resolution_order(arguments, &TeraInspector)?;
+    for (index, arg) in order.iter().enumerate() {
+        if resolved.contains_key(&arg.name) {
+            continue;
+        }
+
+        let before: HashMap<String, String> = order[..index]
+            .iter()
+            .filter_map(|earlier| resolved.get(&earlier.name).map(|value| (earlier.name.clone(), value.clone())))
+            .collect();
+        if ArgumentResolver::applies(arg, arguments, &before)? {
             return Err(ValidationError::ArgumentNotResolved(arg.name.clone()).into());
         }
     }
     Ok(())
 }
 
-/// Render a command template with resolved arguments using Tera.
-/// Replaces `{{ var }}` placeholders with their values.
-///
-/// Autoescaping is off: these render shell commands, where HTML-escaping quotes
-/// and `&`/`<`/`>` would corrupt the command.
-pub fn render_command_template(template: &str, resolved: &HashMap<String, String>) -> Result<String, WorkflowError> {
-    let tera = tera::Tera::default();
-    let mut context = tera::Context::new();
+/// Reject arguments named like the template variables taken from the context (`ctx`, `env`),
+/// which they would otherwise shadow.
+pub fn validate_argument_names(arguments: &[WorkflowArgument]) -> Result<(), WorkflowError> {
+    match arguments.iter().find(|arg| RESERVED_ARGUMENT_NAMES.contains(&arg.name.as_str())) {
+        Some(arg) => Err(ValidationError::ReservedArgumentName(arg.name.clone()).into()),
+        None => Ok(())
+    }
+}
+
+/// `--arg` values by argument name. Giving an argument twice is an error rather than letting
+/// the last value win.
+pub fn provided_arguments(arguments: &[(String, String)]) -> Result<HashMap<String, String>, WorkflowError> {
+    let mut provided = HashMap::with_capacity(arguments.len());
+    for (name, value) in arguments {
+        if provided.insert(name.clone(), value.clone()).is_some() {
+            return Err(ValidationError::DuplicateArgument(name.clone()).into());
+        }
+    }
+    Ok(provided)
+}
+
+/// Use the active profile's defaults over the workflow's own for the arguments they name
+pub fn apply_argument_defaults(arguments: &mut [WorkflowArgument], defaults: &BTreeMap<String, String>) {
+    for arg in arguments {
+        if let Some(value) = defaults.get(&arg.name) {
+            arg.default_value = Some(value.clone());
+        }
+    }
+}
+
+/// Render the workflow's command, quoting each argument placeholder as the argument's `quote`
+/// setting asks, with the rules of the workflow's first shell. Values reach filters unquoted,
+/// values without an argument are inserted as given and arguments skipped by their `when`
+/// condition render empty. `context` is available to the template as `ctx` and `env`.
+pub fn render_workflow_command(
+    workflow: &Workflow,
+    resolved: &HashMap<String, String>,
+    context: &WorkflowContext
+) -> Result<String, WorkflowError> {
+    render_workflow_template(workflow, &workflow.command, resolved, context)
+}
 
-    for (key, value) in resolved {
-        context.insert(key.clone(), value);
+/// Render one of the workflow's templates (its command or a step) the way
+/// [`render_workflow_command`] renders the command
+pub fn render_workflow_template(
+    workflow: &Workflow,
+    template: &str,
+    resolved: &HashMap<String, String>,
+    context: &WorkflowContext
+) -> Result<String, WorkflowError> {
+    let mut values = resolved.clone();
+    for arg in &workflow.arguments {
+        values.entry(arg.name.clone()).or_default();
     }
 
-    tera.render_str(template, &context, false).map_err(|e| {
-        WorkflowError::from(ValidationError::InvalidState(t_params!(
-            "error_failed_to_render_command_template",
-            &[&e.to_string()]
-        )))
+    render_for_dialect(&quote_placeholders(template, &workflow.arguments), &values, workflow.dialect(), Some(context))
+}
+
+/// Render with the template library, `shell_quote` bound to `dialect`
+fn render_for_dialect(
+    template: &str,
+    values: &HashMap<String, String>,
+    dialect: ShellDialect,
+    workflow_context: Option<&WorkflowContext>
+) -> Result<String, WorkflowError> {
+    render_template(template, values, dialect, workflow_context).map_err(|e| {
+        WorkflowError::from(ValidationError::InvalidState(t_params!("error_failed_to_render_command_template", &[&e])))
     })
 }
 
@@ -61,19 +130,32 @@ impl Command for ResolveArgumentsCommand {
 
     async fn load(
         &self,
-        _context: &EngineContext,
+        context: &EngineContext,
         app_context: &AppContext,
         current_state: &WorkflowState
     ) -> Result<Self::LoadedData, Self::Error> {
-        let workflow = match current_state {
+        let mut workflow = match current_state {
             WorkflowState::WorkflowStarted(state) => state.selected_workflow.clone(),
             _ => return Err(ValidationError::InvalidState(t!("error_no_workflow_started_to_resolve_arguments")).into())
         };
+        validate_argument_names(&workflow.arguments)?;
+        apply_argument_defaults(&mut workflow.arguments, &app_context.config.argument_defaults);
+
+        let options = ResolveOptions {
+            provided:     provided_arguments(&self.arguments)?,
+            no_prompt:    self.no_prompt,
+            cwd:          PathBuf::from(&context.workflow_context.working_directory),
+            workflow_dir: workflow.directory().unwrap_or_else(|| app_context.config.workflows_dir.clone()),
+            context:      Some(context.workflow_context.clone()),
+            page_size:    app_context.config.settings.ui.page_size
+        };
 
-        let resolved_arguments = ArgumentResolver::resolve_workflow_arguments(
+        let resolved_arguments = ArgumentResolver::resolve_workflow_arguments_with(
             &workflow.arguments,
+            &options,
             &*app_context.prompt,
-            &*app_context.executor
+            &*app_context.executor,
+            &app_context.filesystem
         )
         .await
         .map_err(|e| {
@@ -93,16 +175,26 @@ impl Command for ResolveArgumentsCommand {
     async fn emit(
         &self,
         loaded_data: &Self::LoadedData,
-        _context: &EngineContext,
-        _app_context: &AppContext,
+        context: &EngineContext,
+        app_context: &AppContext,
         current_state: &WorkflowState
     ) -> Result<Vec<WorkflowEvent>, Self::Error> {
         match current_state {
-            WorkflowState::WorkflowStarted(_) => {
+            WorkflowState::WorkflowStarted(state) => {
+                // Secret values stay in memory; the event only records that they were given
+                let arguments = app_context.secrets.conceal(
+                    &state.execution_id,
+                    &loaded_data.workflow.arguments,
+                    &loaded_data.resolved_arguments
+                );
+                let workflow = &loaded_data.workflow;
+                let templates = std::iter::once(workflow.command.as_str())
+                    .chain(workflow.steps.iter().map(|step| step.command.as_str()));
                 let event = WorkflowArgumentsResolvedEvent {
-                    event_id:  Uuid::new_v4().to_string(),
+                    event_id: Uuid::new_v4().to_string(),
                     timestamp: Utc::now(),
-                    arguments: loaded_data.resolved_arguments.clone()
+                    arguments,
+                    context: Some(recorded_context(&context.workflow_context, &workflow.arguments, templates))
                 };
 
                 Ok(vec![WorkflowEvent::WorkflowArgumentsResolved(event)])
@@ -116,31 +208,22 @@ impl Command for ResolveArgumentsCommand {
         _loaded_data: &Self::LoadedData,
         _previous_state: &WorkflowState,
         current_state: &WorkflowState,
-        _context: &EngineContext,
+        context: &EngineContext,
         app_context: &AppContext
     ) -> Result<(), Self::Error> {
         match current_state {
+            // Steps are rendered and handed out one by one when they run
+            WorkflowState::WorkflowArgumentsResolved(state) if state.selected_workflow.is_multi_step() => {}
             WorkflowState::WorkflowArgumentsResolved(state) => {
-                let rendered_command =
-                    render_command_template(&state.selected_workflow.command, &state.resolved_arguments)?;
-
-                app_context.output.step(&rendered_command);
-
-                let should_copy = app_context.prompt.confirm(&t!("prompt_copy_to_clipboard"), true).unwrap_or(true);
-
-                if !should_copy {
-                    app_context.output.info(&t!("cli_clipboard_copy_skipped"));
-                    return Ok(());
-                }
-
-                match super::copy_to_clipboard(&rendered_command) {
-                    Ok(()) => {
-                        app_context.output.success(&t!("cli_command_copied_to_clipboard"));
-                    }
-                    Err(e) => {
-                        app_context.output.warning(&t_params!("cli_failed_to_copy_to_clipboard", &[&e.to_string()]));
-                        app_context.output.info(&t!("cli_command_can_now_be_pasted_and_executed_in_terminal"));
-                    }
+                let workflow = &state.selected_workflow;
+                let shown_command =
+                    render_workflow_command(workflow, &state.resolved_arguments, &context.workflow_context)?;
+
+                app_context.output.step(&shown_command);
+                if !(self.execute || workflow.execute) {
+                    let arguments = app_context.secrets.reveal(&state.execution_id, &state.resolved_arguments);
+                    let rendered_command = render_workflow_command(workflow, &arguments, &context.workflow_context)?;
+                    app_context.sink.deliver(&rendered_command, !self.no_prompt)?;
                 }
             }
             _ => {
@@ -170,7 +253,7 @@ impl Command for ResolveArgumentsCommand {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::workflow::{ArgumentType, WorkflowArgument};
+    use crate::domain::workflow::{ArgumentType, QuoteStyle, WorkflowArgument};
 
     fn text_arg(name: &str) -> WorkflowArgument {
         WorkflowArgument {
@@ -182,9 +265,15 @@ mod tests {
             enum_command:       None,
             enum_variants:      None,
             dynamic_resolution: None,
+            depends_on:         vec![],
             multi:              false,
             min_selections:     None,
-            max_selections:     None
+            max_selections:     None,
+            quote:              None,
+            validation:         None,
+            path:               None,
+            when:               None,
+            expression:         None
         }
     }
 
@@ -198,6 +287,18 @@ mod tests {
         assert!(validate_all_resolved(&args, &resolved).is_ok());
     }
 
+    #[test]
+    fn profile_defaults_replace_workflow_defaults() {
+        let mut args = vec![text_arg("namespace"), text_arg("pod")];
+        args[0].default_value = Some("default".to_string());
+        args[1].default_value = Some("api".to_string());
+        let defaults = BTreeMap::from([("namespace".to_string(), "acme-prod".to_string())]);
+
+        apply_argument_defaults(&mut args, &defaults);
+        assert_eq!(args[0].default_value.as_deref(), Some("acme-prod"));
+        assert_eq!(args[1].default_value.as_deref(), Some("api"));
+    }
+
     #[test]
     fn validate_fails_when_argument_missing() {
         let args = vec![text_arg("env"), text_arg("region")];
@@ -208,13 +309,30 @@ mod tests {
         assert!(result.is_err());
     }
 
+    fn scale_args() -> Vec<WorkflowArgument> {
+        let replicas = WorkflowArgument { when: Some(r#"action == "scale""#.to_string()), ..text_arg("replicas") };
+        vec![text_arg("action"), replicas]
+    }
+
+    #[test]
+    fn validate_accepts_arguments_skipped_by_their_condition() {
+        let resolved = HashMap::from([("action".to_string(), "restart".to_string())]);
+        assert!(validate_all_resolved(&scale_args(), &resolved).is_ok());
+
+        let resolved = HashMap::from([("action".to_string(), "scale".to_string())]);
+        assert!(matches!(
+            validate_all_resolved(&scale_args(), &resolved),
+            Err(WorkflowError::Validation(ValidationError::ArgumentNotResolved(name))) if name == "replicas"
+        ));
+    }
+
     #[test]
     fn render_template_replaces_placeholders() {
-        let mut resolved = HashMap::new();
-        resolved.insert("name".to_string(), "world".to_string());
-        resolved.insert("count".to_string(), "3".to_string());
+        let wf = workflow("echo {{ name }} {{ count }}", vec!["bash"], vec![]);
+        let resolved =
+            HashMap::from([("name".to_string(), "world".to_string()), ("count".to_string(), "3".to_string())]);
 
-        let result = render_command_template("echo {{ name }} {{ count }}", &resolved).unwrap();
+        let result = render_workflow_command(&wf, &resolved, &test_context()).unwrap();
         assert_eq!(result, "echo world 3");
     }
 
@@ -222,17 +340,220 @@ mod tests {
     /// turn them into HTML entities and silently corrupt every command using them.
     #[test]
     fn render_template_does_not_escape_shell_metacharacters() {
-        let mut resolved = HashMap::new();
-        resolved.insert("msg".to_string(), r#"a "quoted" & <redirected> 'value'"#.to_string());
+        let wf = workflow("echo {{ msg }}", vec!["bash"], vec![]);
+        let resolved = HashMap::from([("msg".to_string(), r#"a "quoted" & <redirected> 'value'"#.to_string())]);
 
-        let result = render_command_template("echo {{ msg }}", &resolved).unwrap();
+        let result = render_workflow_command(&wf, &resolved, &test_context()).unwrap();
         assert_eq!(result, r#"echo a "quoted" & <redirected> 'value'"#);
     }
 
+    fn workflow(command: &str, shells: Vec<&str>, arguments: Vec<WorkflowArgument>) -> Workflow {
+        Workflow {
+            name: "wf".to_string(),
+            description: String::new(),
+            command: command.to_string(),
+            arguments,
+            tags: vec![],
+            source_url: None,
+            author: None,
+            author_url: None,
+            execute: false,
+            steps: vec![],
+            shells: shells.into_iter().map(String::from).collect(),
+            namespace: None,
+            origin: None
+        }
+    }
+
+    fn test_context() -> WorkflowContext {
+        WorkflowContext {
+            session_id:        "session-1".to_string(),
+            user:              "alice".to_string(),
+            hostname:          "box".to_string(),
+            working_directory: "/home/alice/project".to_string(),
+            timestamp:         chrono::DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z").unwrap().to_utc(),
+            env:               HashMap::from([("KUBECONFIG".to_string(), "/tmp/kube".to_string())]),
+            cli_args:          vec!["wf".to_string(), "run".to_string()]
+        }
+    }
+
+    #[test]
+    fn workflow_command_sees_context_variables() {
+        let wf = workflow(
+            "echo {{ ctx.user }}@{{ ctx.hostname }} {{ ctx.cwd }} {{ ctx.timestamp }} {{ env.KUBECONFIG }} {{ \
+             ctx.args | join(\" \") }}",
+            vec![],
+            vec![]
+        );
+
+        let result = render_workflow_command(&wf, &HashMap::new(), &test_context()).unwrap();
+        assert_eq!(result, "echo alice@box /home/alice/project 2024-05-01T12:00:00+00:00 /tmp/kube wf run");
+    }
+
+    #[test]
+    fn env_variable_and_env_function_coexist() {
+        let wf = workflow(r#"{{ env.KUBECONFIG }} {{ env("WF_TEST_SURELY_UNSET", default="x") }}"#, vec![], vec![]);
+        assert_eq!(render_workflow_command(&wf, &HashMap::new(), &test_context()).unwrap(), "/tmp/kube x");
+    }
+
+    #[test]
+    fn reserved_argument_names_are_rejected() {
+        assert!(validate_argument_names(&[text_arg("user"), text_arg("cwd")]).is_ok());
+        for name in ["ctx", "env"] {
+            let err = validate_argument_names(&[text_arg("msg"), text_arg(name)]).unwrap_err();
+            assert!(matches!(err, WorkflowError::Validation(ValidationError::ReservedArgumentName(n)) if n == name));
+        }
+    }
+
+    #[test]
+    fn an_argument_given_twice_is_rejected() {
+        let assignment = |name: &str, value: &str| (name.to_string(), value.to_string());
+        let provided = provided_arguments(&[assignment("a", "1"), assignment("b", "2")]).unwrap();
+        assert_eq!(provided.get("a").map(String::as_str), Some("1"));
+
+        let err = provided_arguments(&[assignment("a", "1"), assignment("b", "2"), assignment("a", "2")]).unwrap_err();
+        assert!(matches!(err, WorkflowError::Validation(ValidationError::DuplicateArgument(n)) if n == "a"));
+    }
+
+    #[test]
+    fn skipped_arguments_render_empty() {
+        let wf = workflow(
+            "kubectl {{ action }}{% if replicas %} --replicas {{ replicas }}{% endif %}",
+            vec!["bash"],
+            scale_args()
+        );
+        let resolved = HashMap::from([("action".to_string(), "restart".to_string())]);
+        assert_eq!(render_workflow_command(&wf, &resolved, &test_context()).unwrap(), "kubectl restart");
+    }
+
+    #[test]
+    fn workflow_command_quotes_text_values_by_default() {
+        let wf = workflow("git commit -m {{ msg }}", vec!["bash"], vec![text_arg("msg")]);
+        let resolved = HashMap::from([("msg".to_string(), "fix: it's done; rm -rf /".to_string())]);
+
+        let result = render_workflow_command(&wf, &resolved, &test_context()).unwrap();
+        assert_eq!(result, r"git commit -m 'fix: it'\''s done; rm -rf /'");
+    }
+
+    #[test]
+    fn workflow_command_respects_quote_none_and_double() {
+        let raw = WorkflowArgument { quote: Some(QuoteStyle::Raw), ..text_arg("flags") };
+        let double = WorkflowArgument { quote: Some(QuoteStyle::Double), ..text_arg("name") };
+        let wf = workflow("ls {{ flags }} {{ name }}", vec![], vec![raw, double]);
+        let resolved = HashMap::from([
+            ("flags".to_string(), "-la --color".to_string()),
+            ("name".to_string(), "my $dir".to_string())
+        ]);
+
+        assert_eq!(render_workflow_command(&wf, &resolved, &test_context()).unwrap(), r#"ls -la --color "my \$dir""#);
+    }
+
+    #[test]
+    fn already_quoted_placeholders_are_escaped_for_their_quotes() {
+        let wf = workflow(r#"git commit -m "{{ msg }}" && echo '{{ msg }}'"#, vec!["bash"], vec![text_arg("msg")]);
+        let resolved = HashMap::from([("msg".to_string(), r#"it's "$HOME""#.to_string())]);
+
+        let result = render_workflow_command(&wf, &resolved, &test_context()).unwrap();
+        assert_eq!(result, r#"git commit -m "it's \"\$HOME\"" && echo 'it'\''s "$HOME"'"#);
+    }
+
+    #[test]
+    fn shell_quote_filter_quotes_text_values_once() {
+        let wf = workflow("echo {{ msg | shell_quote }} {{ msg }}", vec!["bash"], vec![text_arg("msg")]);
+        let resolved = HashMap::from([("msg".to_string(), "it's".to_string())]);
+
+        assert_eq!(render_workflow_command(&wf, &resolved, &test_context()).unwrap(), r"echo 'it'\''s' 'it'\''s'");
+    }
+
+    #[test]
+    fn shell_quote_filter_follows_first_shell() {
+        let wf = workflow("echo {{ msg | shell_quote }}", vec!["pwsh", "bash"], vec![text_arg("msg")]);
+        let resolved = HashMap::from([("msg".to_string(), "it's".to_string())]);
+
+        assert_eq!(render_workflow_command(&wf, &resolved, &test_context()).unwrap(), "echo 'it''s'");
+    }
+
+    /// Render through the workflow path, every value being a default (`quote: auto`) Text argument
+    fn render(template: &str, values: &[(&str, &str)]) -> String {
+        let resolved: HashMap<String, String> = values.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let wf = workflow(template, vec!["bash"], values.iter().map(|(name, _)| text_arg(name)).collect());
+        render_workflow_template(&wf, template, &resolved, &test_context()).unwrap()
+    }
+
+    #[test]
+    fn split_and_join_take_positional_separators() {
+        let result = render(r#"kubectl logs {{ services | split(",") | join(" -s ") }}"#, &[("services", "api,web")]);
+        assert_eq!(result, "kubectl logs api -s web");
+
+        let result = render(r#"{{ files | split(",") | join(" ") }} {{ files }}"#, &[("files", "a b,c")]);
+        assert_eq!(result, "a b c 'a b,c'");
+    }
+
+    #[test]
+    fn shell_quote_filter_quotes_unsafe_values() {
+        assert_eq!(render("echo {{ msg | shell_quote }}", &[("msg", "a b")]), "echo 'a b'");
+        assert_eq!(render("echo {{ msg | shell_quote }}", &[("msg", "plain")]), "echo plain");
+        assert_eq!(render("echo {{ msg | shell_quote }}", &[("msg", "it's")]), r"echo 'it'\''s'");
+    }
+
+    #[test]
+    fn default_filter_replaces_missing_and_empty_values() {
+        assert_eq!(render(r#"{{ tag | default("latest") }}"#, &[("tag", "")]), "latest");
+        assert_eq!(render(r#"{{ tag | default(value="latest") }}"#, &[("tag", "v1")]), "v1");
+        assert_eq!(render(r#"{{ missing | default("latest") }}"#, &[]), "latest");
+    }
+
+    #[test]
+    fn slugify_filter_slugs_values() {
+        assert_eq!(render("{{ title | slugify }}", &[("title", "Fix the Login Bug!")]), "fix-the-login-bug");
+    }
+
+    #[test]
+    fn basename_and_dirname_split_paths() {
+        let values = [("path", "/var/log/app.log")];
+        assert_eq!(render("{{ path | basename }}", &values), "app.log");
+        assert_eq!(render("{{ path | dirname }}", &values), "/var/log");
+    }
+
+    #[test]
+    fn upper_and_lower_change_case() {
+        assert_eq!(
+            render("{{ stage | upper }}-{{ region | lower }}", &[("stage", "prod"), ("region", "EU")]),
+            "PROD-eu"
+        );
+    }
+
+    #[test]
+    fn b64encode_filter_encodes_values() {
+        assert_eq!(render("{{ secret | b64encode }}", &[("secret", "user:pass")]), "dXNlcjpwYXNz");
+    }
+
+    #[test]
+    fn env_function_reads_variables_with_fallback() {
+        let path = std::env::var("PATH").unwrap();
+        assert_eq!(render(r#"{{ env("PATH") }}"#, &[]), path);
+        assert_eq!(render(r#"{{ env("WF_TEST_SURELY_UNSET", default="none") }}"#, &[]), "none");
+        assert_eq!(render(r#"{{ env(name="WF_TEST_SURELY_UNSET") }}"#, &[]), "");
+    }
+
+    #[test]
+    fn now_function_formats_local_time() {
+        let year = chrono::Local::now().format("%Y").to_string();
+        assert_eq!(render(r#"{{ now("%Y") }}"#, &[]), year);
+        assert!(chrono::DateTime::parse_from_rfc3339(&render("{{ now() }}", &[])).is_ok());
+    }
+
+    #[test]
+    fn uuid_function_generates_v4_ids() {
+        let id = render("{{ uuid() }}", &[]);
+        assert_eq!(Uuid::parse_str(&id).unwrap().get_version_num(), 4);
+        assert_ne!(id, render("{{ uuid() }}", &[]));
+    }
+
     #[test]
     fn render_template_error_on_missing_variable() {
-        let resolved = HashMap::new();
-        let result = render_command_template("echo {{ missing }}", &resolved);
+        let wf = workflow("echo {{ missing }}", vec!["bash"], vec![]);
+        let result = render_workflow_command(&wf, &HashMap::new(), &test_context());
         assert!(result.is_err());
     }
 }
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+
+use crate::{
+    AppContext,
+    adapter::schema::workflow_schema,
+    domain::{
+        command::PrintSchemaCommand,
+        engine::EngineContext,
+        error::{StorageError, WorkflowError},
+        event::WorkflowEvent,
+        state::WorkflowState
+    },
+    port::command::Command
+};
+
+#[async_trait]
+impl Command for PrintSchemaCommand {
+    type Error = WorkflowError;
+    type LoadedData = String;
+
+    async fn load(
+        &self,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        serde_json::to_string_pretty(&workflow_schema())
+            .map_err(|e| WorkflowError::Storage(StorageError::Serialization(e.to_string())))
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        Ok(vec![])
+    }
+
+    async fn effect(
+        &self,
+        loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        _current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        app_context.output.raw(loaded_data);
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "print-schema"
+    }
+
+    fn description(&self) -> &'static str {
+        "Prints the JSON Schema of workflow files"
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn is_mutating(&self) -> bool {
+        false
+    }
+}
@@ -9,6 +9,7 @@ use crate::{
         engine::EngineContext,
         error::{ValidationError, WorkflowError},
         event::{WorkflowEvent, WorkflowSelectedEvent},
+        shell::Shell,
         state::WorkflowState,
         workflow::Workflow
     },
@@ -20,39 +21,68 @@ use crate::{
 };
 
 /// Build the hint shown next to a workflow in the chooser.
-/// Format: "description • #tag1 #tag2", omitting either half if empty.
+/// Format: "origin • namespace/ • description • #tag1 #tag2", omitting any part that is empty.
 pub fn workflow_hint(workflow: &Workflow) -> String {
-    let tags = if workflow.tags.is_empty() {
-        String::new()
-    } else {
-        workflow.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")
-    };
+    let origin = workflow.origin.as_ref().map(|origin| origin.label.clone()).unwrap_or_default();
+    let namespace = workflow.namespace.as_ref().map(|namespace| format!("{}/", namespace)).unwrap_or_default();
+    let tags = workflow.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ");
 
-    match (workflow.description.is_empty(), tags.is_empty()) {
-        (true, true) => String::new(),
-        (false, true) => workflow.description.clone(),
-        (true, false) => tags,
-        (false, false) => format!("{} • {}", workflow.description, tags)
-    }
+    [origin, namespace, workflow.description.clone(), tags]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" • ")
 }
 
-/// Select a workflow from a list using the UserPrompt trait.
+/// Select a workflow from a list using the UserPrompt trait, `page_size` rows at a time.
 /// Returns the selected Workflow.
-pub fn select_workflow(prompt: &dyn UserPrompt, workflows: &[Workflow]) -> Result<Workflow, WorkflowError> {
+pub fn select_workflow(
+    prompt: &dyn UserPrompt,
+    workflows: &[Workflow],
+    page_size: usize
+) -> Result<Workflow, WorkflowError> {
+    select_workflow_titled(prompt, workflows, &t!("select_workflow"), page_size)
+}
+
+/// Same as [`select_workflow`] with a custom prompt title.
+pub fn select_workflow_titled(
+    prompt: &dyn UserPrompt,
+    workflows: &[Workflow],
+    title: &str,
+    page_size: usize
+) -> Result<Workflow, WorkflowError> {
     let options: Vec<SelectOption> =
-        workflows.iter().map(|w| SelectOption::new(w.name.clone(), workflow_hint(w))).collect();
+        workflows.iter().map(|w| SelectOption::new(chooser_label(w, workflows), workflow_hint(w))).collect();
 
     let selected_name = prompt
-        .select(&t!("select_workflow"), options, 10)
+        .select(title, options, page_size)
         .map_err(|e| ValidationError::SelectionFailed("workflow".to_string(), e.to_string()))?;
 
     workflows
         .iter()
-        .find(|w| w.name == selected_name)
+        .find(|w| chooser_label(w, workflows) == selected_name)
         .cloned()
         .ok_or_else(|| ValidationError::InvalidState(t_params!("error_workflow_not_found", &[&selected_name])).into())
 }
 
+/// Name shown in the chooser, prefixed with the namespace when another workflow has the same name
+fn chooser_label(workflow: &Workflow, workflows: &[Workflow]) -> String {
+    let shared = workflows.iter().filter(|w| w.name == workflow.name).count() > 1;
+    match &workflow.namespace {
+        Some(namespace) if shared => format!("{}/{}", namespace, workflow.name),
+        _ => workflow.name.clone()
+    }
+}
+
+/// Keep only the workflows usable in `shell`. Errors when none are left to choose from.
+pub fn filter_by_shell(workflows: &[Workflow], shell: Shell) -> Result<Vec<Workflow>, WorkflowError> {
+    let matching: Vec<Workflow> = workflows.iter().filter(|w| w.supports_shell(shell)).cloned().collect();
+    if matching.is_empty() {
+        return Err(ValidationError::InvalidState(t_params!("error_no_workflows_for_shell", &[shell.name()])).into());
+    }
+    Ok(matching)
+}
+
 #[async_trait]
 impl Command for InteractivelySelectWorkflowCommand {
     type Error = WorkflowError;
@@ -65,7 +95,11 @@ impl Command for InteractivelySelectWorkflowCommand {
         current_state: &WorkflowState
     ) -> Result<Self::LoadedData, Self::Error> {
         if let WorkflowState::WorkflowsDiscovered(state) = current_state {
-            let workflow = select_workflow(&*app_context.prompt, &state.discovered_workflows)?;
+            let workflows = match self.shell {
+                Some(shell) => filter_by_shell(&state.discovered_workflows, shell)?,
+                None => state.discovered_workflows.clone()
+            };
+            let workflow = select_workflow(&*app_context.prompt, &workflows, app_context.config.settings.ui.page_size)?;
             Ok(InteractivelySelectWorkflowData { workflow })
         } else {
             Err(ValidationError::InvalidState(t!("error_workflows_not_discovered_yet")).into())
@@ -128,10 +162,13 @@ impl Command for InteractivelySelectWorkflowCommand {
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
     use super::*;
     use crate::{
         adapter::prompt::mock::{MockPrompt, MockPromptResponse},
-        domain::error::PromptError
+        domain::{error::PromptError, workflow::WorkflowOrigin},
+        service::settings::DEFAULT_PAGE_SIZE
     };
 
     fn test_workflow(name: &str) -> Workflow {
@@ -143,8 +180,12 @@ mod tests {
             source_url:  None,
             author:      None,
             author_url:  None,
+            execute:     false,
+            steps:       vec![],
             shells:      vec![],
-            tags:        vec![]
+            tags:        vec![],
+            namespace:   None,
+            origin:      None
         }
     }
 
@@ -153,17 +194,29 @@ mod tests {
         let workflows = vec![test_workflow("deploy"), test_workflow("build"), test_workflow("test")];
         let prompt = MockPrompt::new(vec![MockPromptResponse::Select("build".to_string())]);
 
-        let result = select_workflow(&prompt, &workflows).unwrap();
+        let result = select_workflow(&prompt, &workflows, DEFAULT_PAGE_SIZE).unwrap();
         assert_eq!(result.name, "build");
     }
 
+    #[test]
+    fn same_names_are_told_apart_by_namespace() {
+        let mut k8s = test_workflow("logs");
+        k8s.namespace = Some("k8s".to_string());
+        let mut aws = test_workflow("logs");
+        aws.namespace = Some("aws".to_string());
+        let prompt = MockPrompt::new(vec![MockPromptResponse::Select("aws/logs".to_string())]);
+
+        let result = select_workflow(&prompt, &[k8s, aws], DEFAULT_PAGE_SIZE).unwrap();
+        assert_eq!(result.namespace.as_deref(), Some("aws"));
+    }
+
     #[test]
     fn returns_error_on_prompt_failure() {
         let workflows = vec![test_workflow("deploy")];
         let prompt =
             MockPrompt::new(vec![MockPromptResponse::Error(PromptError::Interaction("cancelled".to_string()).into())]);
 
-        let result = select_workflow(&prompt, &workflows);
+        let result = select_workflow(&prompt, &workflows, DEFAULT_PAGE_SIZE);
         assert!(result.is_err());
     }
 
@@ -172,7 +225,7 @@ mod tests {
         let workflows = vec![test_workflow("deploy")];
         let prompt = MockPrompt::new(vec![MockPromptResponse::Select("nonexistent".to_string())]);
 
-        let result = select_workflow(&prompt, &workflows);
+        let result = select_workflow(&prompt, &workflows, DEFAULT_PAGE_SIZE);
         assert!(result.is_err());
     }
 
@@ -185,8 +238,12 @@ mod tests {
             source_url:  None,
             author:      None,
             author_url:  None,
+            execute:     false,
+            steps:       vec![],
             shells:      vec![],
-            tags:        tags.into_iter().map(String::from).collect()
+            tags:        tags.into_iter().map(String::from).collect(),
+            namespace:   None,
+            origin:      None
         }
     }
 
@@ -208,6 +265,34 @@ mod tests {
         assert_eq!(workflow_hint(&wf), "Deploys the app • #k8s #deploy");
     }
 
+    #[test]
+    fn filter_by_shell_keeps_matching_and_shell_agnostic_workflows() {
+        let mut fish_only = test_workflow("fish-only");
+        fish_only.shells = vec!["fish".to_string()];
+        let workflows = vec![test_workflow("any"), fish_only];
+
+        let names: Vec<String> = filter_by_shell(&workflows, Shell::Zsh).unwrap().into_iter().map(|w| w.name).collect();
+        assert_eq!(names, vec!["any"]);
+        assert_eq!(filter_by_shell(&workflows, Shell::Fish).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn filter_by_shell_errors_when_nothing_matches() {
+        let mut bash_only = test_workflow("bash-only");
+        bash_only.shells = vec!["bash".to_string()];
+        assert!(filter_by_shell(&[bash_only], Shell::Fish).is_err());
+    }
+
+    #[test]
+    fn hint_starts_with_the_namespace() {
+        let mut wf = workflow_with("Tails pod logs", vec!["k8s"]);
+        wf.namespace = Some("k8s".to_string());
+        assert_eq!(workflow_hint(&wf), "k8s/ • Tails pod logs • #k8s");
+
+        wf.origin = Some(WorkflowOrigin { dir: PathBuf::from("/repo/.wf"), label: "./.wf".to_string() });
+        assert_eq!(workflow_hint(&wf), "./.wf • k8s/ • Tails pod logs • #k8s");
+    }
+
     #[test]
     fn hint_is_empty_when_both_empty() {
         let wf = workflow_with("", vec![]);
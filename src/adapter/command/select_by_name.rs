@@ -0,0 +1,276 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
+use uuid::Uuid;
+
+use super::select::select_workflow_titled;
+use crate::{
+    AppContext,
+    domain::{
+        command::{SelectWorkflowByNameCommand, SelectWorkflowByNameData},
+        engine::EngineContext,
+        error::{ValidationError, WorkflowError},
+        event::{WorkflowEvent, WorkflowSelectedEvent},
+        state::WorkflowState,
+        workflow::{Workflow, slugify}
+    },
+    port::command::Command,
+    t, t_params
+};
+
+/// Outcome of looking a workflow up by name
+#[derive(Debug)]
+pub enum WorkflowMatch<'a> {
+    /// Exactly one workflow matched
+    Found(&'a Workflow),
+    /// Several workflows matched, best match first
+    Ambiguous(Vec<&'a Workflow>),
+    /// Nothing matched
+    NotFound
+}
+
+/// Find a workflow by exact name or namespaced slug (`k8s/tail-logs`), then by name ignoring case
+/// or slug, then by fuzzy match. Each stage only runs when the previous one found nothing, and
+/// a name shared by workflows of several namespaces is ambiguous at any stage.
+pub fn match_workflow<'a>(query: &str, workflows: &'a [Workflow]) -> WorkflowMatch<'a> {
+    let exact: Vec<&Workflow> = workflows.iter().filter(|w| w.name == query || w.qualified_slug() == query).collect();
+    if !exact.is_empty() {
+        return narrow(exact);
+    }
+
+    let query_slug = slugify(query);
+    let qualified_query = match query.rsplit_once('/') {
+        Some((namespace, name)) => format!("{}/{}", namespace, slugify(name)),
+        None => query_slug.clone()
+    };
+    let loose: Vec<&Workflow> = workflows
+        .iter()
+        .filter(|w| {
+            w.name.eq_ignore_ascii_case(query) || w.slug() == query_slug || w.qualified_slug() == qualified_query
+        })
+        .collect();
+    if !loose.is_empty() {
+        return narrow(loose);
+    }
+
+    let matcher = SkimMatcherV2::default();
+    let mut scored: Vec<(i64, &Workflow)> = workflows
+        .iter()
+        .filter_map(|w| {
+            let by_name = matcher.fuzzy_match(&w.name, query);
+            let by_slug = matcher.fuzzy_match(&w.qualified_slug(), &qualified_query);
+            by_name.max(by_slug).map(|score| (score, w))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+
+    narrow(scored.into_iter().map(|(_, w)| w).collect())
+}
+
+fn narrow(mut matches: Vec<&Workflow>) -> WorkflowMatch<'_> {
+    match matches.len() {
+        0 => WorkflowMatch::NotFound,
+        1 => WorkflowMatch::Found(matches.remove(0)),
+        _ => WorkflowMatch::Ambiguous(matches)
+    }
+}
+
+#[async_trait]
+impl Command for SelectWorkflowByNameCommand {
+    type Error = WorkflowError;
+    type LoadedData = SelectWorkflowByNameData;
+
+    async fn load(
+        &self,
+        _context: &EngineContext,
+        app_context: &AppContext,
+        current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        let WorkflowState::WorkflowsDiscovered(state) = current_state else {
+            return Err(ValidationError::InvalidState(t!("error_workflows_not_discovered_yet")).into());
+        };
+
+        let workflow = match match_workflow(&self.name, &state.discovered_workflows) {
+            WorkflowMatch::Found(workflow) => workflow.clone(),
+            WorkflowMatch::Ambiguous(candidates) if self.no_prompt => {
+                let names = candidates.iter().map(|w| w.qualified_slug()).collect::<Vec<_>>().join(", ");
+                return Err(ValidationError::SelectionFailed(
+                    "workflow".to_string(),
+                    t_params!("error_ambiguous_workflow_name", &[&self.name, &names])
+                )
+                .into());
+            }
+            WorkflowMatch::Ambiguous(candidates) => {
+                let candidates: Vec<Workflow> = candidates.into_iter().cloned().collect();
+                select_workflow_titled(
+                    &*app_context.prompt,
+                    &candidates,
+                    &t_params!("select_workflow_matching", &[&self.name]),
+                    app_context.config.settings.ui.page_size
+                )?
+            }
+            WorkflowMatch::NotFound => {
+                return Err(ValidationError::InvalidState(t_params!("error_workflow_not_found", &[&self.name])).into());
+            }
+        };
+
+        Ok(SelectWorkflowByNameData { workflow })
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        loaded_data: &Self::LoadedData,
+        context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        let event = WorkflowSelectedEvent {
+            event_id:  Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            workflow:  loaded_data.workflow.clone(),
+            user:      context.workflow_context.user.clone()
+        };
+
+        Ok(vec![WorkflowEvent::WorkflowSelected(event)])
+    }
+
+    async fn effect(
+        &self,
+        loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        if let WorkflowState::WorkflowSelected(_) = current_state {
+            app_context.output.step(&loaded_data.workflow.name);
+        } else {
+            app_context.output.warning(&t!("error_no_workflow_selected"));
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "select-workflow-by-name"
+    }
+
+    fn description(&self) -> &'static str {
+        "Selects a discovered workflow by name, slug or fuzzy match"
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn is_mutating(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_workflow(name: &str) -> Workflow {
+        Workflow {
+            name:        name.to_string(),
+            description: String::new(),
+            command:     "echo test".to_string(),
+            arguments:   vec![],
+            source_url:  None,
+            author:      None,
+            author_url:  None,
+            execute:     false,
+            steps:       vec![],
+            shells:      vec![],
+            tags:        vec![],
+            namespace:   None,
+            origin:      None
+        }
+    }
+
+    fn names(result: WorkflowMatch<'_>) -> Vec<String> {
+        match result {
+            WorkflowMatch::Found(w) => vec![w.name.clone()],
+            WorkflowMatch::Ambiguous(ws) => ws.iter().map(|w| w.name.clone()).collect(),
+            WorkflowMatch::NotFound => vec![]
+        }
+    }
+
+    #[test]
+    fn may_prompt_for_an_ambiguous_name() {
+        let command = SelectWorkflowByNameCommand { name: "logs".to_string(), no_prompt: false };
+        assert!(command.is_interactive());
+    }
+
+    #[test]
+    fn exact_name_wins() {
+        let workflows = vec![test_workflow("deploy"), test_workflow("deploy-staging")];
+        assert!(matches!(match_workflow("deploy", &workflows), WorkflowMatch::Found(w) if w.name == "deploy"));
+    }
+
+    #[test]
+    fn matches_by_slug() {
+        let workflows = vec![test_workflow("Deploy to K8s"), test_workflow("Tail logs")];
+        assert!(
+            matches!(match_workflow("deploy-to-k8s", &workflows), WorkflowMatch::Found(w) if w.name == "Deploy to K8s")
+        );
+    }
+
+    #[test]
+    fn namespace_picks_between_same_names() {
+        let mut k8s = test_workflow("Tail logs");
+        k8s.namespace = Some("k8s".to_string());
+        let mut aws = test_workflow("Tail logs");
+        aws.namespace = Some("aws".to_string());
+        let workflows = vec![k8s, aws];
+        let namespace = |query: &str| match match_workflow(query, &workflows) {
+            WorkflowMatch::Found(w) => w.namespace.clone(),
+            _ => None
+        };
+
+        assert_eq!(namespace("aws/tail-logs").as_deref(), Some("aws"));
+        assert_eq!(namespace("aws/Tail logs").as_deref(), Some("aws"));
+    }
+
+    #[test]
+    fn name_shared_across_namespaces_is_ambiguous() {
+        let mut k8s = test_workflow("Tail logs");
+        k8s.namespace = Some("k8s".to_string());
+        let mut aws = test_workflow("Tail logs");
+        aws.namespace = Some("aws".to_string());
+        let workflows = vec![k8s, aws];
+
+        for query in ["Tail logs", "tail-logs"] {
+            let WorkflowMatch::Ambiguous(candidates) = match_workflow(query, &workflows) else {
+                panic!("{} should be ambiguous", query)
+            };
+            let slugs: Vec<String> = candidates.iter().map(|w| w.qualified_slug()).collect();
+            assert_eq!(slugs, vec!["k8s/tail-logs", "aws/tail-logs"]);
+        }
+    }
+
+    #[test]
+    fn unique_fuzzy_match_is_selected() {
+        let workflows = vec![test_workflow("Deploy to K8s"), test_workflow("Tail logs")];
+        assert_eq!(names(match_workflow("tlogs", &workflows)), vec!["Tail logs"]);
+    }
+
+    #[test]
+    fn ambiguous_fuzzy_match_returns_candidates() {
+        let workflows = vec![test_workflow("deploy api"), test_workflow("deploy web"), test_workflow("tail logs")];
+        let result = names(match_workflow("depl", &workflows));
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|n| n.starts_with("deploy")));
+    }
+
+    #[test]
+    fn no_match_returns_not_found() {
+        let workflows = vec![test_workflow("deploy")];
+        assert!(matches!(match_workflow("zzz", &workflows), WorkflowMatch::NotFound));
+    }
+}
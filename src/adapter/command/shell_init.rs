@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+
+use crate::{
+    AppContext,
+    domain::{
+        command::ShellInitCommand, engine::EngineContext, error::WorkflowError, event::WorkflowEvent, shell::Shell,
+        state::WorkflowState
+    },
+    port::command::Command
+};
+
+const ZSH_WIDGET: &str = include_str!("../../../config/shell/wf.zsh");
+const BASH_WIDGET: &str = include_str!("../../../config/shell/wf.bash");
+const FISH_WIDGET: &str = include_str!("../../../config/shell/wf.fish");
+
+/// Build the widget script for `shell`, bound to `key` or the shell's default binding.
+/// The widget runs `wf --print` so only the resolved command comes back on stdout.
+pub fn shell_init_script(shell: Shell, key: Option<&str>) -> String {
+    let template = match shell {
+        Shell::Zsh => ZSH_WIDGET,
+        Shell::Bash => BASH_WIDGET,
+        Shell::Fish => FISH_WIDGET
+    };
+
+    template.replace("{{key}}", key.unwrap_or(shell.default_key()))
+}
+
+#[async_trait]
+impl Command for ShellInitCommand {
+    type Error = WorkflowError;
+    type LoadedData = String;
+
+    async fn load(
+        &self,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        Ok(shell_init_script(self.shell, self.key.as_deref()))
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        _loaded_data: &Self::LoadedData,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        Ok(vec![])
+    }
+
+    async fn effect(
+        &self,
+        loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        _current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        app_context.output.raw(loaded_data.trim_end());
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "shell-init"
+    }
+
+    fn description(&self) -> &'static str {
+        "Prints a line-editor widget for the given shell"
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn is_mutating(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zsh_widget_edits_lbuffer_and_binds_default_key() {
+        let script = shell_init_script(Shell::Zsh, None);
+        assert!(script.contains("wf --print --shell zsh"));
+        assert!(script.contains("LBUFFER="));
+        assert!(script.contains("zle -N _wf_widget"));
+        assert!(script.contains("bindkey '^G' _wf_widget"));
+    }
+
+    #[test]
+    fn bash_widget_inserts_at_readline_point() {
+        let script = shell_init_script(Shell::Bash, None);
+        assert!(script.contains("wf --print --shell bash"));
+        assert!(script.contains("READLINE_LINE="));
+        assert!(script.contains("READLINE_POINT="));
+        assert!(script.contains(r#"bind -x '"\C-g": _wf_widget'"#));
+    }
+
+    #[test]
+    fn fish_widget_uses_commandline() {
+        let script = shell_init_script(Shell::Fish, None);
+        assert!(script.contains("wf --print --shell fish"));
+        assert!(script.contains("commandline -i"));
+        assert!(script.contains(r"bind \cg _wf_widget"));
+    }
+
+    #[test]
+    fn key_override_replaces_default_binding() {
+        let script = shell_init_script(Shell::Zsh, Some("^X^W"));
+        assert!(script.contains("bindkey '^X^W' _wf_widget"));
+        assert!(!script.contains("{{key}}"));
+    }
+}
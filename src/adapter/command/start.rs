@@ -114,8 +114,12 @@ mod tests {
             source_url:  None,
             author:      None,
             author_url:  None,
+            execute:     false,
+            steps:       vec![],
             shells:      vec![],
-            tags:        vec![]
+            tags:        vec![],
+            namespace:   None,
+            origin:      None
         }
     }
 
@@ -0,0 +1,505 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    execute::{failure_message, run_command},
+    resolve::render_workflow_template
+};
+use crate::{
+    AppContext,
+    domain::{
+        command::{RunWorkflowStepsCommand, RunWorkflowStepsData},
+        engine::EngineContext,
+        error::{ValidationError, WorkflowError},
+        event::{WorkflowEvent, WorkflowStepCompletedEvent},
+        state::WorkflowState,
+        workflow::{StepAction, StepRecord}
+    },
+    port::{
+        command::Command,
+        executor::CommandExecutor,
+        output::OutputWriter,
+        prompt::{SelectOption, UserPrompt},
+        sink::ResultSink
+    },
+    t, t_params
+};
+
+/// How the steps of a run are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepMode {
+    /// Run every step (`--exec` or `execute: true`)
+    Execute,
+    /// Hand all steps to the result sink at once (`--no-prompt`)
+    Deliver,
+    /// Ask for each step whether to run, copy or skip it
+    Ask
+}
+
+/// A rendered step ready to be handled
+#[derive(Debug, Clone)]
+pub struct RenderedStep {
+    /// Position in the workflow's `steps`, starting at 0
+    pub index:   usize,
+    pub name:    String,
+    /// Command to run or copy
+    pub command: String,
+    /// Command to show and record, secrets redacted
+    pub shown:   String
+}
+
+/// Handle `steps`, out of `total` in the workflow, in order and record what happened to each one.
+/// Stops after the first executed step that fails.
+pub async fn handle_steps(
+    steps: &[RenderedStep],
+    total: usize,
+    mode: StepMode,
+    prompt: &dyn UserPrompt,
+    executor: &dyn CommandExecutor,
+    sink: &dyn ResultSink,
+    output: &dyn OutputWriter
+) -> Result<Vec<StepRecord>, WorkflowError> {
+    let total = total.to_string();
+    let mut records = Vec::with_capacity(steps.len());
+
+    for step in steps {
+        output.step(&t_params!("workflow_step", &[&(step.index + 1).to_string(), &total, &step.name, &step.shown]));
+
+        let action = match mode {
+            StepMode::Execute => StepAction::Executed,
+            StepMode::Deliver => StepAction::Copied,
+            StepMode::Ask => ask_step_action(prompt)?
+        };
+
+        let mut record = StepRecord {
+            index: step.index,
+            name: step.name.clone(),
+            command: step.shown.clone(),
+            action,
+            exit_code: None,
+            duration_ms: None,
+            error: None
+        };
+
+        match action {
+            StepAction::Executed => {
+                let outcome = run_command(executor, &step.command).await;
+                record.exit_code = outcome.exit_code;
+                record.duration_ms = Some(outcome.duration_ms);
+                record.error = outcome.error;
+            }
+            // Asked for explicitly, so the sink shouldn't ask again
+            StepAction::Copied if mode == StepMode::Ask => sink.deliver(&step.command, false)?,
+            StepAction::Copied | StepAction::Skipped => {}
+        }
+
+        let failed = record.failed();
+        records.push(record);
+        if failed {
+            break;
+        }
+    }
+
+    // Without prompts there's no pause between steps, so they go out together
+    if mode == StepMode::Deliver && !steps.is_empty() {
+        let script = steps.iter().map(|step| step.command.as_str()).collect::<Vec<_>>().join("\n");
+        sink.deliver(&script, false)?;
+    }
+
+    Ok(records)
+}
+
+fn ask_step_action(prompt: &dyn UserPrompt) -> Result<StepAction, WorkflowError> {
+    let run = t!("step_action_run");
+    let copy = t!("step_action_copy");
+    let skip = t!("step_action_skip");
+
+    let options = vec![SelectOption::plain(run.clone()), SelectOption::plain(copy.clone()), SelectOption::plain(skip)];
+    let choice = prompt.select(&t!("prompt_step_action"), options, 3)?;
+
+    Ok(if choice == run {
+        StepAction::Executed
+    } else if choice == copy {
+        StepAction::Copied
+    } else {
+        StepAction::Skipped
+    })
+}
+
+#[async_trait]
+impl Command for RunWorkflowStepsCommand {
+    type Error = WorkflowError;
+    type LoadedData = RunWorkflowStepsData;
+
+    /// The next step runs here rather than in `effect` so it can be emitted as an event; the
+    /// command [continues](Command::continues) with the step after it once that is persisted.
+    /// Without prompts the steps are handed out together, in one pass.
+    async fn load(
+        &self,
+        context: &EngineContext,
+        app_context: &AppContext,
+        current_state: &WorkflowState
+    ) -> Result<Self::LoadedData, Self::Error> {
+        let (workflow, execution_id, resolved_arguments, handled) = match current_state {
+            WorkflowState::WorkflowArgumentsResolved(state) if state.selected_workflow.is_multi_step() => {
+                (&state.selected_workflow, &state.execution_id, &state.resolved_arguments, 0)
+            }
+            WorkflowState::WorkflowStepCompleted(state) if !state.failed() => {
+                (&state.selected_workflow, &state.execution_id, &state.resolved_arguments, state.steps.len())
+            }
+            // Single-command workflow: handled by resolve and execute
+            WorkflowState::WorkflowArgumentsResolved(_)
+            | WorkflowState::WorkflowExecuted(_)
+            | WorkflowState::WorkflowStepCompleted(_) => {
+                return Ok(RunWorkflowStepsData { steps: vec![] });
+            }
+            _ => return Err(ValidationError::InvalidState(t!("error_no_arguments_resolved_to_run_steps")).into())
+        };
+
+        let mode = if self.forced || workflow.execute {
+            StepMode::Execute
+        } else if self.no_prompt {
+            StepMode::Deliver
+        } else {
+            StepMode::Ask
+        };
+        let count = if mode == StepMode::Deliver { workflow.steps.len() } else { 1 };
+
+        let arguments = app_context.secrets.reveal(execution_id, resolved_arguments);
+        let steps = workflow
+            .steps
+            .iter()
+            .enumerate()
+            .skip(handled)
+            .take(count)
+            .map(|(index, step)| {
+                let render =
+                    |values| render_workflow_template(workflow, &step.command, values, &context.workflow_context);
+                Ok(RenderedStep {
+                    index,
+                    name: step.name.clone(),
+                    command: render(&arguments)?,
+                    shown: render(resolved_arguments)?
+                })
+            })
+            .collect::<Result<Vec<_>, WorkflowError>>()?;
+
+        let steps = handle_steps(
+            &steps,
+            workflow.steps.len(),
+            mode,
+            &*app_context.prompt,
+            &*app_context.executor,
+            &*app_context.sink,
+            &*app_context.output
+        )
+        .await?;
+
+        Ok(RunWorkflowStepsData { steps })
+    }
+
+    fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn emit(
+        &self,
+        loaded_data: &Self::LoadedData,
+        _context: &EngineContext,
+        _app_context: &AppContext,
+        _current_state: &WorkflowState
+    ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        Ok(loaded_data
+            .steps
+            .iter()
+            .map(|step| {
+                WorkflowEvent::WorkflowStepCompleted(WorkflowStepCompletedEvent {
+                    event_id:  Uuid::new_v4().to_string(),
+                    timestamp: Utc::now(),
+                    step:      step.clone()
+                })
+            })
+            .collect())
+    }
+
+    async fn effect(
+        &self,
+        loaded_data: &Self::LoadedData,
+        _previous_state: &WorkflowState,
+        current_state: &WorkflowState,
+        _context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), Self::Error> {
+        let WorkflowState::WorkflowStepCompleted(state) = current_state else {
+            // Not a multi-step workflow: nothing ran
+            return Ok(());
+        };
+        if loaded_data.steps.is_empty() {
+            return Ok(());
+        }
+
+        let last = state.steps.last().filter(|step| step.failed());
+        match last {
+            Some(step) => Err(WorkflowError::Execution(t_params!(
+                "error_workflow_step_failed",
+                &[
+                    &step.name,
+                    &failure_message(step.exit_code, step.duration_ms.unwrap_or_default(), step.error.as_deref())
+                ]
+            ))),
+            // More steps to go
+            None if state.steps.len() < state.selected_workflow.steps.len() => Ok(()),
+            None => {
+                let executed = state.steps.iter().filter(|step| step.action == StepAction::Executed).count();
+                app_context.output.success(&t_params!(
+                    "workflow_steps_done",
+                    &[&state.steps.len().to_string(), &executed.to_string()]
+                ));
+                Ok(())
+            }
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "run-workflow-steps"
+    }
+
+    fn description(&self) -> &'static str {
+        "Copies, runs or skips each step of a multi-step workflow"
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn is_mutating(&self) -> bool {
+        true
+    }
+
+    fn runs_processes(&self) -> bool {
+        true
+    }
+
+    /// On to the next step, unless the last one failed or it was the last one
+    fn continues(&self, state: &WorkflowState) -> bool {
+        matches!(
+            state,
+            WorkflowState::WorkflowStepCompleted(state)
+                if !state.failed() && state.steps.len() < state.selected_workflow.steps.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        adapter::{
+            executor::mock::MockExecutor,
+            output::mock::MockOutput,
+            prompt::mock::{MockPrompt, MockPromptResponse},
+            sink::mock::MockSink
+        },
+        domain::{
+            state::WorkflowStepCompletedState,
+            workflow::{Workflow, WorkflowStep}
+        }
+    };
+
+    fn steps(commands: &[&str]) -> Vec<RenderedStep> {
+        commands
+            .iter()
+            .enumerate()
+            .map(|(i, command)| RenderedStep {
+                index:   i,
+                name:    format!("step {}", i + 1),
+                command: command.to_string(),
+                shown:   command.to_string()
+            })
+            .collect()
+    }
+
+    fn executor(results: &[(&str, bool)]) -> MockExecutor {
+        MockExecutor::new(
+            results
+                .iter()
+                .map(|(command, ok)| {
+                    let result =
+                        if *ok { Ok(String::new()) } else { Err(WorkflowError::Execution("failed".to_string())) };
+                    (command.to_string(), result)
+                })
+                .collect::<HashMap<_, _>>()
+        )
+    }
+
+    #[tokio::test]
+    async fn execute_mode_runs_every_step() {
+        let executor = executor(&[("forward", true), ("probe", true)]);
+        let sink = MockSink::default();
+        let records = handle_steps(
+            &steps(&["forward", "probe"]),
+            2,
+            StepMode::Execute,
+            &MockPrompt::new(vec![]),
+            &executor,
+            &sink,
+            &MockOutput::default()
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|r| r.action == StepAction::Executed && r.exit_code == Some(0)));
+        assert!(sink.delivered.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn records_keep_the_redacted_command() {
+        let step = RenderedStep {
+            index:   0,
+            name:    "login".to_string(),
+            command: "login --token s3cr3t".to_string(),
+            shown:   "login --token ******".to_string()
+        };
+        let executor = executor(&[("login --token s3cr3t", true)]);
+        let output = MockOutput::default();
+        let records = handle_steps(
+            &[step],
+            1,
+            StepMode::Execute,
+            &MockPrompt::new(vec![]),
+            &executor,
+            &MockSink::default(),
+            &output
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(records[0].command, "login --token ******");
+        assert_eq!(records[0].exit_code, Some(0));
+        assert!(output.messages().iter().all(|m| !m.contains("s3cr3t")));
+    }
+
+    #[tokio::test]
+    async fn failed_step_stops_the_run() {
+        let executor = executor(&[("forward", false), ("probe", true)]);
+        let records = handle_steps(
+            &steps(&["forward", "probe"]),
+            2,
+            StepMode::Execute,
+            &MockPrompt::new(vec![]),
+            &executor,
+            &MockSink::default(),
+            &MockOutput::default()
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert!(records[0].failed());
+    }
+
+    #[tokio::test]
+    async fn later_steps_keep_their_position() {
+        let records = handle_steps(
+            &steps(&["forward", "probe"])[1..],
+            2,
+            StepMode::Execute,
+            &MockPrompt::new(vec![]),
+            &executor(&[("probe", true)]),
+            &MockSink::default(),
+            &MockOutput::default()
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].index, 1);
+    }
+
+    fn step_state(handled: &[&str], failed: bool) -> WorkflowState {
+        let workflow = Workflow {
+            steps: ["forward", "probe"]
+                .iter()
+                .map(|name| WorkflowStep { name: name.to_string(), command: name.to_string() })
+                .collect(),
+            ..serde_yaml::from_str("name: t\ndescription: d\ncommand: ''\narguments: []\ntags: []\nshells: []").unwrap()
+        };
+        let steps = handled
+            .iter()
+            .enumerate()
+            .map(|(index, name)| StepRecord {
+                index,
+                name: name.to_string(),
+                command: name.to_string(),
+                action: StepAction::Executed,
+                exit_code: Some(if failed { 1 } else { 0 }),
+                duration_ms: Some(1),
+                error: None
+            })
+            .collect();
+        WorkflowState::WorkflowStepCompleted(WorkflowStepCompletedState {
+            discovered_workflows: vec![],
+            selected_workflow: workflow,
+            execution_id: "exec-1".to_string(),
+            resolved_arguments: HashMap::new(),
+            steps
+        })
+    }
+
+    #[test]
+    fn continues_until_every_step_ran_or_one_failed() {
+        let command = RunWorkflowStepsCommand { forced: true, no_prompt: true };
+        assert!(command.continues(&step_state(&["forward"], false)));
+        assert!(!command.continues(&step_state(&["forward"], true)));
+        assert!(!command.continues(&step_state(&["forward", "probe"], false)));
+    }
+
+    #[tokio::test]
+    async fn deliver_mode_hands_out_all_steps_at_once() {
+        let sink = MockSink::default();
+        let records = handle_steps(
+            &steps(&["forward", "probe", "cleanup"]),
+            3,
+            StepMode::Deliver,
+            &MockPrompt::new(vec![]),
+            &executor(&[]),
+            &sink,
+            &MockOutput::default()
+        )
+        .await
+        .unwrap();
+
+        assert!(records.iter().all(|r| r.action == StepAction::Copied));
+        assert_eq!(*sink.delivered.lock().unwrap(), vec!["forward\nprobe\ncleanup".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn ask_mode_follows_each_choice() {
+        let prompt = MockPrompt::new(vec![
+            MockPromptResponse::Select(t!("step_action_copy")),
+            MockPromptResponse::Select(t!("step_action_run")),
+            MockPromptResponse::Select(t!("step_action_skip")),
+        ]);
+        let sink = MockSink::default();
+        let records = handle_steps(
+            &steps(&["forward", "probe", "cleanup"]),
+            3,
+            StepMode::Ask,
+            &prompt,
+            &executor(&[("probe", true)]),
+            &sink,
+            &MockOutput::default()
+        )
+        .await
+        .unwrap();
+
+        let actions: Vec<StepAction> = records.iter().map(|r| r.action).collect();
+        assert_eq!(actions, vec![StepAction::Copied, StepAction::Executed, StepAction::Skipped]);
+        assert_eq!(*sink.delivered.lock().unwrap(), vec!["forward".to_string()]);
+        assert_eq!(records[1].exit_code, Some(0));
+    }
+}
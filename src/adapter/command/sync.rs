@@ -1,5 +1,8 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
 use uuid::Uuid;
 
+use super::remote::{SYNC_MARKER, folder_not_synced, folder_owned_by_sync, unknown_remote};
 use crate::{
     AppContext,
     domain::{
@@ -10,21 +13,63 @@ use crate::{
         state::WorkflowState
     },
     port::{command::Command, git::CloneOptions},
+    service::settings::Remote,
     t, t_params
 };
 
 #[derive(Debug, Clone)]
 pub struct SyncWorkflowsData {
-    pub remote_url: String,
-    pub branch:     String,
-    pub ssh_key:    Option<String>
+    /// Name of the remote, which is also the folder its workflows go into
+    pub name:    String,
+    pub remote:  Remote,
+    /// Remotes to sync after this one
+    pub pending: Vec<String>,
+    /// Folder in the way of the remote, holding workflows `wf sync` didn't create
+    pub blocked: Option<PathBuf>
 }
 
-/// Prepare sync data, applying default remote URL if none provided.
-pub fn prepare_sync_data(remote_url: Option<&str>, branch: &str, ssh_key: Option<&str>) -> SyncWorkflowsData {
-    let remote_url = remote_url.unwrap_or("https://github.com/sagoez/workflow-vault.git").to_string();
+/// Pick the remote to sync now and the ones left for later: the requested remote, or the first
+/// configured one (followed by all the others) when none is named.
+pub fn plan_sync(
+    remotes: &BTreeMap<String, Remote>,
+    requested: Option<&str>,
+    pending: &[String]
+) -> Result<SyncWorkflowsData, WorkflowError> {
+    let (name, pending) = match requested {
+        Some(name) => (name.to_string(), pending.to_vec()),
+        None => {
+            let mut names = remotes.keys().cloned();
+            let first = names.next().ok_or_else(|| WorkflowError::Config(t!("error_no_remotes")))?;
+            (first, names.collect())
+        }
+    };
+    let remote = remotes.get(&name).cloned().ok_or_else(|| unknown_remote(&name, remotes))?;
+
+    Ok(SyncWorkflowsData { name, remote, pending, blocked: None })
+}
 
-    SyncWorkflowsData { remote_url, branch: branch.to_string(), ssh_key: ssh_key.map(|s| s.to_string()) }
+/// What comes after a remote in a run: syncing the next of the `pending` remotes, nothing once
+/// all are done, or an error naming the remotes that `failed` if any did
+pub fn next_sync(pending: &[String], failed: Vec<String>) -> Result<Option<SyncWorkflowsCommand>, WorkflowError> {
+    match pending.split_first() {
+        Some((next, rest)) => {
+            Ok(Some(SyncWorkflowsCommand { remote: Some(next.clone()), pending: rest.to_vec(), failed }))
+        }
+        None if failed.is_empty() => Ok(None),
+        None => Err(WorkflowError::Network(t_params!("error_remotes_not_synced", &[&failed.join(", ")])))
+    }
+}
+
+/// Schedule what [`next_sync`] says comes next
+pub async fn continue_sync(
+    context: &EngineContext,
+    pending: &[String],
+    failed: Vec<String>
+) -> Result<(), WorkflowError> {
+    match next_sync(pending, failed)? {
+        Some(sync_command) => context.schedule_command(sync_command.into()).await,
+        None => Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -35,10 +80,19 @@ impl Command for SyncWorkflowsCommand {
     async fn load(
         &self,
         _context: &EngineContext,
-        _app_context: &AppContext,
+        app_context: &AppContext,
         _current_state: &WorkflowState
     ) -> Result<Self::LoadedData, Self::Error> {
-        Ok(prepare_sync_data(self.remote_url.as_deref(), &self.branch, self.ssh_key.as_deref()))
+        let settings = &app_context.config.settings;
+        let mut data = plan_sync(&settings.remotes, self.remote.as_deref(), &self.pending)?;
+        data.remote.ssh_key = data.remote.ssh_key.or_else(|| settings.sync.ssh_key.clone());
+
+        // Syncing replaces the folder's contents, so never touch one holding hand-written workflows
+        let destination = app_context.config.workflows_dir.join(&data.name);
+        if !folder_owned_by_sync(app_context.filesystem.as_ref(), &destination) {
+            data.blocked = Some(destination);
+        }
+        Ok(data)
     }
 
     fn validate(&self, _loaded_data: &Self::LoadedData) -> Result<(), Self::Error> {
@@ -52,12 +106,16 @@ impl Command for SyncWorkflowsCommand {
         _app_context: &AppContext,
         _current_state: &WorkflowState
     ) -> Result<Vec<WorkflowEvent>, Self::Error> {
+        if loaded_data.blocked.is_some() {
+            return Ok(vec![]);
+        }
         let event = SyncRequestedEvent {
             event_id:   Uuid::new_v4().to_string(),
             timestamp:  chrono::Utc::now(),
-            remote_url: loaded_data.remote_url.clone(),
-            branch:     loaded_data.branch.clone(),
-            ssh_key:    loaded_data.ssh_key.clone()
+            remote_url: loaded_data.remote.url.clone(),
+            branch:     loaded_data.remote.branch.clone(),
+            ssh_key:    loaded_data.remote.ssh_key.clone(),
+            remote:     Some(loaded_data.name.clone())
         };
 
         Ok(vec![WorkflowEvent::SyncRequested(event)])
@@ -65,29 +123,47 @@ impl Command for SyncWorkflowsCommand {
 
     async fn effect(
         &self,
-        _loaded_data: &Self::LoadedData,
+        loaded_data: &Self::LoadedData,
         _previous_state: &WorkflowState,
         current_state: &WorkflowState,
         context: &EngineContext,
         app_context: &AppContext
     ) -> Result<(), Self::Error> {
+        if let Some(folder) = &loaded_data.blocked {
+            return self.skip(loaded_data, folder_not_synced(folder), context, app_context).await;
+        }
+
         match current_state {
             WorkflowState::SyncRequested(state) => {
-                let workflows_dir = &app_context.config.workflows_dir;
-                let clone_options =
-                    CloneOptions { ssh_key: state.ssh_key.clone(), branch: Some(state.branch.clone()) };
+                let destination = app_context.config.workflows_dir.join(&loaded_data.name);
+                let clone_options = CloneOptions {
+                    ssh_key: state.ssh_key.clone(),
+                    branch:  Some(state.branch.clone()),
+                    path:    loaded_data.remote.path.clone()
+                };
 
-                app_context.output.intro(&t_params!("cli_synced_workflows", &[&state.remote_url]));
+                app_context.output.intro(&t_params!("cli_syncing_remote", &[&loaded_data.name, &state.remote_url]));
 
                 let spinner = app_context.output.spinner();
                 spinner.start(&t_params!("git_cloning_from", &[&state.remote_url]));
 
-                let commit_id =
-                    app_context.git_client.clone_repository(&state.remote_url, workflows_dir, &clone_options).await?;
+                let cloned = app_context.git_client.clone_repository(&state.remote_url, &destination, &clone_options);
+                let commit_id = match cloned.await {
+                    Ok(commit_id) => commit_id,
+                    Err(e) => {
+                        spinner.stop(&t!("git_clone_failed"));
+                        return self.skip(loaded_data, e, context, app_context).await;
+                    }
+                };
 
+                app_context.filesystem.write(&destination.join(SYNC_MARKER), &state.remote_url)?;
                 spinner.stop(&t_params!("git_clone_success", &[&commit_id[..8]]));
 
-                let record_sync_result_command = RecordSyncResultCommand { commit_id: commit_id.clone() };
+                let record_sync_result_command = RecordSyncResultCommand {
+                    commit_id: commit_id.clone(),
+                    pending:   loaded_data.pending.clone(),
+                    failed:    self.failed.clone()
+                };
                 context.schedule_command(record_sync_result_command.into()).await?;
             }
             _ => {
@@ -102,23 +178,65 @@ impl Command for SyncWorkflowsCommand {
     }
 }
 
+impl SyncWorkflowsCommand {
+    /// Give up on the remote being synced: fail with `error` when it is the only one, otherwise
+    /// report it and go on with the others
+    async fn skip(
+        &self,
+        loaded_data: &SyncWorkflowsData,
+        error: WorkflowError,
+        context: &EngineContext,
+        app_context: &AppContext
+    ) -> Result<(), WorkflowError> {
+        if loaded_data.pending.is_empty() && self.failed.is_empty() {
+            return Err(error);
+        }
+        app_context.output.warning(&t_params!("remote_sync_failed", &[&loaded_data.name, &error.to_string()]));
+        let failed = self.failed.iter().cloned().chain([loaded_data.name.clone()]).collect();
+        continue_sync(context, &loaded_data.pending, failed).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn remotes() -> BTreeMap<String, Remote> {
+        BTreeMap::from([
+            ("ops".to_string(), Remote::new("git@example.com:ops/workflows.git")),
+            ("vault".to_string(), Remote::new("https://example.com/vault.git"))
+        ])
+    }
+
+    #[test]
+    fn syncs_every_remote_when_none_is_named() {
+        let data = plan_sync(&remotes(), None, &[]).unwrap();
+        assert_eq!(data.name, "ops");
+        assert_eq!(data.remote.url, "git@example.com:ops/workflows.git");
+        assert_eq!(data.pending, vec!["vault"]);
+    }
+
+    #[test]
+    fn syncs_only_the_named_remote() {
+        let data = plan_sync(&remotes(), Some("vault"), &[]).unwrap();
+        assert_eq!(data.remote.branch, "main");
+        assert!(data.pending.is_empty());
+    }
+
     #[test]
-    fn prepare_data_uses_default_url_when_none() {
-        let data = prepare_sync_data(None, "main", None);
-        assert_eq!(data.remote_url, "https://github.com/sagoez/workflow-vault.git");
-        assert_eq!(data.branch, "main");
-        assert!(data.ssh_key.is_none());
+    fn a_failed_remote_does_not_stop_the_others() {
+        let pending = vec!["vault".to_string(), "team".to_string()];
+        let next = next_sync(&pending, vec!["ops".to_string()]).unwrap().unwrap();
+        assert_eq!(next.remote.as_deref(), Some("vault"));
+        assert_eq!((next.pending, next.failed), (vec!["team".to_string()], vec!["ops".to_string()]));
+
+        assert!(next_sync(&[], vec![]).unwrap().is_none());
+        assert!(next_sync(&[], vec!["ops".to_string()]).is_err());
     }
 
     #[test]
-    fn prepare_data_uses_explicit_url_and_ssh_key() {
-        let data = prepare_sync_data(Some("git@github.com:user/repo.git"), "develop", Some("/path/to/key"));
-        assert_eq!(data.remote_url, "git@github.com:user/repo.git");
-        assert_eq!(data.branch, "develop");
-        assert_eq!(data.ssh_key.as_deref(), Some("/path/to/key"));
+    fn unknown_or_missing_remotes_are_errors() {
+        assert!(plan_sync(&remotes(), Some("team"), &[]).is_err());
+        assert!(plan_sync(&BTreeMap::new(), None, &[]).is_err());
     }
 }
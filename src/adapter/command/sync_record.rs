@@ -5,6 +5,7 @@ use uuid::Uuid;
 
 use crate::{
     AppContext,
+    adapter::{command::sync::continue_sync, loader::find_workflow_files},
     domain::{
         command::RecordSyncResultCommand,
         engine::EngineContext,
@@ -24,18 +25,13 @@ pub struct RecordSyncResultData {
     pub synced_count: u32
 }
 
-/// Count YAML/YML workflow files in a directory using the FileSystem trait.
+/// Count YAML/YML workflow files in a directory and its subfolders using the FileSystem trait.
 pub fn count_workflow_files(fs: &dyn FileSystem, dir: &Path) -> Result<u32, WorkflowError> {
     if !fs.exists(dir) {
         return Ok(0);
     }
 
-    let entries = fs.read_dir_entries(dir)?;
-    let count =
-        entries.iter().filter(|p| p.extension().map(|ext| ext == "yaml" || ext == "yml").unwrap_or(false)).count()
-            as u32;
-
-    Ok(count)
+    Ok(find_workflow_files(fs, dir)?.len() as u32)
 }
 
 #[async_trait]
@@ -59,9 +55,12 @@ impl Command for RecordSyncResultCommand {
             }
         };
 
-        let workflows_dir = &app_context.config.workflows_dir;
+        let workflows_dir = match &sync_state.remote {
+            Some(remote) => app_context.config.workflows_dir.join(remote),
+            None => app_context.config.workflows_dir.clone()
+        };
         let commit_id = self.commit_id.clone();
-        let synced_count = count_workflow_files(&*app_context.filesystem, workflows_dir)?;
+        let synced_count = count_workflow_files(&*app_context.filesystem, &workflows_dir)?;
 
         Ok(RecordSyncResultData {
             remote_url: sync_state.remote_url.clone(),
@@ -99,12 +98,15 @@ impl Command for RecordSyncResultCommand {
         _loaded_data: &Self::LoadedData,
         _previous_state: &WorkflowState,
         current_state: &WorkflowState,
-        _context: &EngineContext,
+        context: &EngineContext,
         app_context: &AppContext
     ) -> Result<(), Self::Error> {
         match current_state {
             WorkflowState::WorkflowsSynced(state) => {
                 app_context.output.outro(&t_params!("cli_synced_count", &[&state.synced_count.to_string()]));
+
+                // Remotes are synced one after another, each recording its own result
+                continue_sync(context, &self.pending, self.failed.clone()).await?;
             }
             _ => {
                 app_context.output.warning(&t!("error_no_workflows_synced"));
@@ -133,9 +135,11 @@ mod tests {
         fs.write(&dir.join("a.yaml"), "content").unwrap();
         fs.write(&dir.join("b.yml"), "content").unwrap();
         fs.write(&dir.join("c.txt"), "content").unwrap();
+        fs.create_dir_all(&dir.join("k8s")).unwrap();
+        fs.write(&dir.join("k8s/logs.yaml"), "content").unwrap();
 
         let count = count_workflow_files(&fs, dir).unwrap();
-        assert_eq!(count, 2);
+        assert_eq!(count, 3);
     }
 
     #[test]
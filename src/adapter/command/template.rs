@@ -0,0 +1,533 @@
+//! Filters and functions available in command templates
+//!
+//! On top of Tera's builtins (`upper`, `lower`, `split`, `join`, ...), templates get
+//! `shell_quote`, `slugify`, `basename`, `dirname`, `b64encode`, a `default` that also
+//! replaces empty values, and the `env()`, `now()` and `uuid()` functions. The same
+//! library evaluates the `when:` conditions of arguments.
+//!
+//! Argument values go into templates unquoted; [`quote_placeholders`] marks the bare
+//! `{{ name }}` placeholders whose output gets quoted for the shell.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::Serialize;
+use tera::{Context, Kwargs, State, Tera, TeraResult, Value};
+
+use crate::{
+    domain::{
+        shell::ShellDialect,
+        workflow::{ArgumentType, QuoteStyle, WorkflowArgument, WorkflowContext, slugify}
+    },
+    port::template::TemplateInspector
+};
+
+/// Filters and functions whose first argument may be given without a name,
+/// e.g. `split(",")` for `split(pat=",")`
+const POSITIONAL_ARGUMENTS: &[(&str, &str)] =
+    &[("split", "pat"), ("join", "sep"), ("default", "value"), ("env", "name"), ("now", "format")];
+
+/// Tera instance with the workflow filter and function library registered.
+/// `shell_quote` quotes with the rules of `dialect`.
+pub fn template_engine(dialect: ShellDialect) -> Tera {
+    let mut tera = Tera::default();
+
+    tera.register_filter("shell_quote", move |value: &str, _: Kwargs, _: &State| {
+        dialect.quote(value, QuoteStyle::Auto)
+    });
+    tera.register_filter("quote_argument", move |value: &str, kwargs: Kwargs, _: &State| {
+        quote_argument(dialect, value, kwargs)
+    });
+    tera.register_filter("slugify", |value: &str, _: Kwargs, _: &State| slugify(value));
+    tera.register_filter("basename", basename);
+    tera.register_filter("dirname", dirname);
+    tera.register_filter("b64encode", |value: &str, _: Kwargs, _: &State| STANDARD.encode(value));
+    tera.register_filter("default", default);
+
+    tera.register_function("env", env);
+    tera.register_function("now", now);
+    tera.register_function("uuid", |_: Kwargs, _: &State| uuid::Uuid::new_v4().to_string());
+
+    tera
+}
+
+/// Render `template` with `values` and, when `workflow_context` is given, the `ctx` and `env`
+/// variables. The error is the template library's message.
+///
+/// Autoescaping is off: these render shell commands, where HTML-escaping quotes
+/// and `&`/`<`/`>` would corrupt the command.
+pub fn render_template(
+    template: &str,
+    values: &HashMap<String, String>,
+    dialect: ShellDialect,
+    workflow_context: Option<&WorkflowContext>
+) -> Result<String, String> {
+    let mut context = Context::new();
+    if let Some(workflow_context) = workflow_context {
+        insert_context_variables(&mut context, workflow_context);
+    }
+    for (key, value) in values {
+        context.insert(key.clone(), value);
+    }
+
+    template_engine(dialect)
+        .render_str(&expand_positional_arguments(template), &context, false)
+        .map_err(|e| e.to_string())
+}
+
+/// A `{{ name }}` placeholder printing an argument as it is, without filters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholder {
+    /// The argument printed
+    pub name:    String,
+    /// Quote character open where the placeholder sits, as in `"{{ name }}"` or `"fix: {{ name }}"`
+    pub within:  Option<char>,
+    /// Whether those quotes hold nothing but the placeholder, as in `"{{ name }}"`
+    pub wrapped: bool,
+    /// Byte range of the whole `{{ ... }}` tag
+    range:       std::ops::Range<usize>
+}
+
+/// The bare placeholders of `template`, in order. Tags with filters, tests or anything else
+/// than a single name aren't placeholders. The text between tags is read the way a POSIX shell
+/// reads it to tell which quotes are open at each placeholder.
+pub fn placeholders(template: &str) -> Vec<Placeholder> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    let mut open = None;
+
+    while let Some(start) = template[offset..].find('{').map(|at| offset + at) {
+        let closing = match template[start + 1..].chars().next() {
+            Some('{') => "}}",
+            Some('%') => "%}",
+            Some('#') => "#}",
+            _ => {
+                open = open_quote(&template[offset..start + 1], open);
+                offset = start + 1;
+                continue;
+            }
+        };
+        open = open_quote(&template[offset..start], open);
+        let Some(end) = template[start + 2..].find(closing).map(|at| start + at + 4) else {
+            break;
+        };
+
+        let name = template[start + 2..end - 2].trim_matches('-').trim();
+        let is_name = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+        if closing == "}}" && is_name {
+            let before = template[..start].chars().next_back();
+            let after = template[end..].chars().next();
+            found.push(Placeholder {
+                name:    name.to_string(),
+                within:  open,
+                wrapped: open.is_some() && before == open && after == open,
+                range:   start..end
+            });
+        }
+        offset = end;
+    }
+
+    found
+}
+
+/// The quote left open after reading `text` as shell words, starting inside `open`. Backslashes
+/// escape outside quotes and inside double quotes; single quotes take everything literally.
+fn open_quote(text: &str, mut open: Option<char>) -> Option<char> {
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (open, c) {
+            (Some('\''), '\'') => open = None,
+            (Some('\''), _) => {}
+            (_, '\\') => {
+                chars.next();
+            }
+            (None, '\'' | '"') => open = Some(c),
+            (Some('"'), '"') => open = None,
+            _ => {}
+        }
+    }
+    open
+}
+
+/// Make the bare placeholders of `arguments` in `template` quote their value the way the
+/// argument's `quote` setting asks, as the last step of rendering it. A placeholder inside quotes
+/// the template opens, right around it or earlier in the same string, is escaped for those quotes
+/// instead. Placeholders with filters print
+/// what the filters make of the value as it is; `shell_quote` quotes there.
+pub fn quote_placeholders(template: &str, arguments: &[WorkflowArgument]) -> String {
+    let mut quoted = String::with_capacity(template.len());
+    let mut copied = 0;
+
+    for placeholder in placeholders(template) {
+        let Some(arg) = arguments.iter().find(|arg| arg.name == placeholder.name) else {
+            continue;
+        };
+        let style = match arg.quote_style() {
+            QuoteStyle::Raw => continue,
+            QuoteStyle::Auto => "auto",
+            QuoteStyle::Single => "single",
+            QuoteStyle::Double => "double"
+        };
+        let filter = match placeholder.within {
+            Some('"') => format!(r#"quote_argument(style="{}", within="double")"#, style),
+            Some(_) => format!(r#"quote_argument(style="{}", within="single")"#, style),
+            None => format!(r#"quote_argument(style="{}")"#, style)
+        };
+
+        let tag = &template[placeholder.range.clone()];
+        quoted.push_str(&template[copied..placeholder.range.start]);
+        quoted.push_str(&tag.replacen(&placeholder.name, &format!("{} | {}", placeholder.name, filter), 1));
+        copied = placeholder.range.end;
+    }
+
+    quoted.push_str(&template[copied..]);
+    quoted
+}
+
+/// The filter [`quote_placeholders`] adds: quote as `style` asks, or escape for the quotes
+/// named by `within`
+fn quote_argument(dialect: ShellDialect, value: &str, kwargs: Kwargs) -> TeraResult<String> {
+    let style = match kwargs.must_get::<&str>("style")? {
+        "single" => QuoteStyle::Single,
+        "double" => QuoteStyle::Double,
+        _ => QuoteStyle::Auto
+    };
+    Ok(match kwargs.get::<&str>("within")? {
+        Some("double") => dialect.escape_within(value, '"'),
+        Some(_) => dialect.escape_within(value, '\''),
+        None => dialect.quote(value, style)
+    })
+}
+
+/// The `ctx` template variable
+#[derive(Serialize)]
+struct SessionVariables<'a> {
+    user:       &'a str,
+    hostname:   &'a str,
+    cwd:        &'a str,
+    timestamp:  String,
+    session_id: &'a str,
+    args:       &'a [String]
+}
+
+/// Add `ctx` (user, hostname, cwd, timestamp, session_id, args) and `env` to a template context
+pub fn insert_context_variables(tera_context: &mut Context, context: &WorkflowContext) {
+    let session = SessionVariables {
+        user:       &context.user,
+        hostname:   &context.hostname,
+        cwd:        &context.working_directory,
+        timestamp:  context.timestamp.to_rfc3339(),
+        session_id: &context.session_id,
+        args:       &context.cli_args
+    };
+    tera_context.insert("ctx", &session);
+    tera_context.insert("env", &context.env);
+}
+
+/// Evaluate a `when:` condition such as `action == "scale"` against argument values.
+/// Boolean arguments are booleans and numbers compare as numbers; arguments without a value
+/// are empty strings. The error is the template library's message.
+pub fn evaluate_condition(
+    expression: &str,
+    arguments: &[WorkflowArgument],
+    values: &HashMap<String, String>
+) -> Result<bool, String> {
+    let mut context = Context::new();
+    for arg in arguments {
+        let value = values.get(&arg.name).map(String::as_str).unwrap_or_default();
+        match arg.arg_type {
+            ArgumentType::Boolean => context.insert(arg.name.clone(), &(value == "true")),
+            ArgumentType::Number => match value.trim().parse::<i64>() {
+                Ok(number) => context.insert(arg.name.clone(), &number),
+                Err(_) => match value.trim().parse::<f64>() {
+                    Ok(number) => context.insert(arg.name.clone(), &number),
+                    Err(_) => context.insert(arg.name.clone(), value)
+                }
+            },
+            _ => context.insert(arg.name.clone(), value)
+        }
+    }
+
+    let template = expand_positional_arguments(&format!("{{% if {} %}}true{{% endif %}}", expression));
+    template_engine(ShellDialect::default())
+        .render_str(&template, &context, false)
+        .map(|rendered| rendered == "true")
+        .map_err(|e| e.to_string())
+}
+
+/// Top-level variables `template` reads, e.g. `namespace` for `{{ namespace | upper }}` or `ctx`
+/// for `{{ ctx.user }}`; loop and `set` variables are left out. Fails on a syntax error, with the
+/// template library's message.
+pub fn template_variables(template: &str) -> Result<HashSet<String>, String> {
+    let mut tera = template_engine(ShellDialect::default());
+    tera.add_raw_template("template", &expand_positional_arguments(template)).map_err(|e| e.to_string())?;
+    let variables = tera.get_template_variables("template").map_err(|e| e.to_string())?;
+    Ok(variables.into_iter().map(str::to_string).collect())
+}
+
+/// Variables a `when:` condition uses, parsed the way [`evaluate_condition`] renders it
+pub fn condition_variables(condition: &str) -> Result<HashSet<String>, String> {
+    template_variables(&format!("{{% if {} %}}{{% endif %}}", condition))
+}
+
+/// [`TemplateInspector`] reading templates the way they are rendered
+pub struct TeraInspector;
+
+impl TemplateInspector for TeraInspector {
+    fn variables(&self, template: &str) -> Result<HashSet<String>, String> {
+        template_variables(template)
+    }
+
+    fn condition_variables(&self, condition: &str) -> Result<HashSet<String>, String> {
+        condition_variables(condition)
+    }
+}
+
+fn basename(value: &str, _: Kwargs, _: &State) -> String {
+    Path::new(value).file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+fn dirname(value: &str, _: Kwargs, _: &State) -> String {
+    Path::new(value).parent().map(|parent| parent.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+/// Like Tera's `default`, but an empty string also counts as missing
+fn default(value: Value, kwargs: Kwargs, _: &State) -> TeraResult<Value> {
+    let fallback = kwargs.must_get::<Value>("value")?;
+    let missing = value.is_undefined() || value.is_none() || value.as_str().is_some_and(str::is_empty);
+    Ok(if missing { fallback } else { value })
+}
+
+/// `env("VAR")`: the variable's value, the `default` argument when unset, or an empty string
+fn env(kwargs: Kwargs, _: &State) -> TeraResult<String> {
+    let name = kwargs.must_get::<&str>("name")?;
+    let fallback = kwargs.get::<&str>("default")?.unwrap_or_default();
+    Ok(std::env::var(name).unwrap_or_else(|_| fallback.to_string()))
+}
+
+/// `now("%Y-%m-%d")`: local time in the given strftime format, RFC 3339 without one
+fn now(kwargs: Kwargs, _: &State) -> TeraResult<String> {
+    let now = chrono::Local::now();
+    Ok(match kwargs.get::<&str>("format")? {
+        Some(format) => now.format(format).to_string(),
+        None => now.to_rfc3339()
+    })
+}
+
+/// Rewrite positional first arguments of the filters and functions in [`POSITIONAL_ARGUMENTS`]
+/// into the keyword form Tera requires, e.g. `join(" -s ")` into `join(sep=" -s ")`.
+/// Only text inside `{{ }}` and `{% %}` is touched, and string literals are left alone.
+pub fn expand_positional_arguments(template: &str) -> String {
+    let chars: Vec<char> = template.chars().collect();
+    let mut expanded = String::with_capacity(template.len());
+    let mut in_tag = false;
+    let mut in_string: Option<char> = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if !in_tag {
+            if c == '{' && matches!(next, Some('{' | '%')) {
+                in_tag = true;
+                expanded.push(c);
+                expanded.push(chars[i + 1]);
+                i += 2;
+            } else {
+                expanded.push(c);
+                i += 1;
+            }
+            continue;
+        }
+
+        if let Some(quote) = in_string {
+            if c == quote {
+                in_string = None;
+            }
+            expanded.push(c);
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '"' | '\'' | '`' => {
+                in_string = Some(c);
+                expanded.push(c);
+                i += 1;
+            }
+            '}' | '%' if next == Some('}') => {
+                in_tag = false;
+                expanded.push(c);
+                expanded.push('}');
+                i += 2;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let ident: String = chars[start..i].iter().collect();
+                expanded.push_str(&ident);
+
+                if chars.get(i) == Some(&'(')
+                    && let Some((_, keyword)) = POSITIONAL_ARGUMENTS.iter().find(|(name, _)| *name == ident)
+                {
+                    expanded.push('(');
+                    i += 1;
+                    let rest = &chars[i..];
+                    let first = rest.iter().position(|c| !c.is_whitespace()).unwrap_or(rest.len());
+                    if rest.get(first) != Some(&')') && !starts_with_keyword_argument(&rest[first..]) {
+                        expanded.push_str(keyword);
+                        expanded.push('=');
+                    }
+                }
+            }
+            _ => {
+                expanded.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    expanded
+}
+
+/// Whether `rest` starts with `name=` (but not `name ==`)
+fn starts_with_keyword_argument(rest: &[char]) -> bool {
+    if !rest.first().is_some_and(|c| c.is_alphabetic() || *c == '_') {
+        return false;
+    }
+    let end = rest.iter().position(|c| !(c.is_alphanumeric() || *c == '_')).unwrap_or(rest.len());
+    let after = rest[end..].iter().position(|c| !c.is_whitespace()).map(|offset| end + offset);
+    matches!(after, Some(at) if rest[at] == '=' && rest.get(at + 1) != Some(&'='))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positional_first_argument_gets_keyword() {
+        assert_eq!(expand_positional_arguments(r#"{{ a | join(" ") }}"#), r#"{{ a | join(sep=" ") }}"#);
+        assert_eq!(expand_positional_arguments(r#"{{ now( "%Y" ) }}"#), r#"{{ now(format= "%Y" ) }}"#);
+    }
+
+    #[test]
+    fn keyword_and_empty_arguments_are_untouched() {
+        for template in [r#"{{ a | join(sep=",") }}"#, "{{ now() }}", "{{ uuid() }}"] {
+            assert_eq!(expand_positional_arguments(template), template);
+        }
+    }
+
+    #[test]
+    fn comparison_is_not_mistaken_for_keyword() {
+        assert_eq!(expand_positional_arguments("{{ a | default(b == c) }}"), "{{ a | default(value=b == c) }}");
+    }
+
+    #[test]
+    fn text_outside_tags_and_strings_is_untouched() {
+        let template = r#"join(x) {{ "join(y)" }} {% if a %}{{ b | split(",") }}{% endif %} env(z)"#;
+        assert_eq!(
+            expand_positional_arguments(template),
+            r#"join(x) {{ "join(y)" }} {% if a %}{{ b | split(pat=",") }}{% endif %} env(z)"#
+        );
+    }
+
+    fn condition_args() -> Vec<WorkflowArgument> {
+        serde_yaml::from_str(
+            r#"
+- { name: action, description: "" }
+- { name: replicas, arg_type: Number, description: "" }
+- { name: force, arg_type: Boolean, description: "" }
+"#
+        )
+        .unwrap()
+    }
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn condition_compares_typed_values() {
+        let args = condition_args();
+        let resolved = values(&[("action", "scale"), ("replicas", "10"), ("force", "false")]);
+
+        assert_eq!(evaluate_condition(r#"action == "scale""#, &args, &resolved), Ok(true));
+        assert_eq!(evaluate_condition("replicas > 3", &args, &resolved), Ok(true));
+        assert_eq!(evaluate_condition("force", &args, &resolved), Ok(false));
+        assert_eq!(evaluate_condition(r#"action in ["restart", "stop"]"#, &args, &resolved), Ok(false));
+    }
+
+    #[test]
+    fn condition_sees_missing_values_as_empty() {
+        let args = condition_args();
+        assert_eq!(evaluate_condition(r#"action == """#, &args, &HashMap::new()), Ok(true));
+        assert_eq!(evaluate_condition("action", &args, &HashMap::new()), Ok(false));
+    }
+
+    #[test]
+    fn invalid_condition_is_an_error() {
+        assert!(evaluate_condition("action ==", &condition_args(), &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn placeholders_are_bare_names_only() {
+        let found =
+            placeholders(r#"a {{ x }} "{{- y -}}" {{ z | upper }} '{{ w }}" {% if v %}{{ ctx.user }}{% endif %}"#);
+        let names: Vec<_> = found.iter().map(|p| (p.name.as_str(), p.within)).collect();
+        assert_eq!(names, vec![("x", None), ("y", Some('"')), ("w", Some('\''))]);
+        assert!(found[1].wrapped && !found[2].wrapped);
+    }
+
+    #[test]
+    fn placeholders_inside_a_longer_string_are_within_its_quotes() {
+        let found = placeholders(r#"git commit -m "fix: {{ msg }} ({{ id }})" {{ path }} 'it''s {{ x }}' \"{{ y }}"#);
+        let names: Vec<_> = found.iter().map(|p| (p.name.as_str(), p.within, p.wrapped)).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("msg", Some('"'), false),
+                ("id", Some('"'), false),
+                ("path", None, false),
+                ("x", Some('\''), false),
+                ("y", None, false)
+            ]
+        );
+        assert_eq!(placeholders(r#"echo "a \" {{ x }}""#)[0].within, Some('"'));
+        assert_eq!(placeholders(r#"{% if a == "b" %}{{ x }}{% endif %}"#)[0].within, None);
+    }
+
+    #[test]
+    fn mid_string_placeholder_is_escaped_not_quoted() {
+        let template = quote_placeholders(r#"git commit -m "fix: {{ action }}""#, &condition_args());
+        let resolved = values(&[("action", r#"a "b" $c"#)]);
+        assert_eq!(
+            render_template(&template, &resolved, ShellDialect::Posix, None).unwrap(),
+            r#"git commit -m "fix: a \"b\" \$c""#
+        );
+    }
+
+    #[test]
+    fn quote_placeholders_skips_raw_and_unknown_names() {
+        let args = condition_args();
+        let template = "{{ action }} {{ replicas }} {{ other }} {{ action | upper }}";
+        assert_eq!(
+            quote_placeholders(template, &args),
+            r#"{{ action | quote_argument(style="auto") }} {{ replicas }} {{ other }} {{ action | upper }}"#
+        );
+    }
+
+    #[test]
+    fn template_variables_leave_out_loop_variables() {
+        let variables = template_variables(r#"{% for x in items | split(",") %}{{ x }} {{ ctx.user }}{% endfor %}"#);
+        assert_eq!(variables.unwrap(), HashSet::from(["items".to_string(), "ctx".to_string()]));
+        assert!(template_variables("{{ broken").is_err());
+    }
+}
@@ -4,12 +4,14 @@ use crate::{
     domain::{
         event::{
             AggregateReplayedEvent, AvailableWorkflowsListedEvent, LanguageSetEvent, SyncRequestedEvent,
-            WorkflowArgumentsResolvedEvent, WorkflowCompletedEvent, WorkflowDiscoveredEvent, WorkflowEvent,
-            WorkflowSelectedEvent, WorkflowStartedEvent, WorkflowsSyncedEvent
+            WorkflowArgumentsResolvedEvent, WorkflowCompletedEvent, WorkflowDiscoveredEvent,
+            WorkflowDiscoveryFailedEvent, WorkflowEvent, WorkflowExecutedEvent, WorkflowExecutionFailedEvent,
+            WorkflowSelectedEvent, WorkflowStartedEvent, WorkflowStepCompletedEvent, WorkflowsSyncedEvent
         },
         state::{
             LanguageSetState, SyncRequestedState, WorkflowArgumentsResolvedState, WorkflowCompletedState,
-            WorkflowSelectedState, WorkflowStartedState, WorkflowState, WorkflowsDiscoveredState, WorkflowsListedState,
+            WorkflowExecutedState, WorkflowExecutionFailedState, WorkflowSelectedState, WorkflowStartedState,
+            WorkflowState, WorkflowStepCompletedState, WorkflowsDiscoveredState, WorkflowsListedState,
             WorkflowsSyncedState
         }
     },
@@ -29,9 +31,9 @@ impl Event for WorkflowDiscoveredEvent {
                 Some(WorkflowState::WorkflowsDiscovered(WorkflowsDiscoveredState::new(vec![self.workflow.clone()])))
             }
             WorkflowState::WorkflowsDiscovered(state) => {
-                // Add workflow if not already discovered
+                // Add workflow if not already discovered; namespaces keep same-named workflows apart
                 let mut workflows = state.discovered_workflows.clone();
-                if !workflows.iter().any(|w| w.name == self.workflow.name) {
+                if !workflows.iter().any(|w| w.name == self.workflow.name && w.namespace == self.workflow.namespace) {
                     workflows.push(self.workflow.clone());
                 }
                 Some(WorkflowState::WorkflowsDiscovered(WorkflowsDiscoveredState::new(workflows)))
@@ -65,12 +67,56 @@ impl Event for WorkflowDiscoveredEvent {
     }
 }
 
+impl Event for WorkflowDiscoveryFailedEvent {
+    fn apply(&self, current_state: Option<&WorkflowState>) -> Option<WorkflowState> {
+        let default_state = WorkflowState::default();
+        let current = current_state.unwrap_or(&default_state);
+
+        match current {
+            // The file is recorded, discovery carries on with the workflows that did load
+            WorkflowState::Initial(_) | WorkflowState::WorkflowsDiscovered(_) => Some(current.clone()),
+            _ => None // Invalid transition - only happens while discovering workflows
+        }
+    }
+
+    fn event_type(&self) -> &'static str {
+        "workflow-discovery-failed"
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    fn event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    fn state_type(&self) -> &'static str {
+        "workflow-state"
+    }
+
+    fn clone_event(&self) -> Box<dyn Event> {
+        Box::new(self.clone())
+    }
+}
+
 impl Event for WorkflowSelectedEvent {
     fn apply(&self, current_state: Option<&WorkflowState>) -> Option<WorkflowState> {
         let default_state = WorkflowState::default();
         let current = current_state.unwrap_or(&default_state);
 
         match current {
+            WorkflowState::Initial(_) => {
+                // Loaded straight from a file - nothing was discovered beforehand
+                Some(WorkflowState::WorkflowSelected(WorkflowSelectedState::new(
+                    vec![self.workflow.clone()],
+                    self.workflow.clone()
+                )))
+            }
             WorkflowState::WorkflowsDiscovered(state) => {
                 // Validate that the selected workflow exists in discovered workflows
                 if state.discovered_workflows.iter().any(|w| w.name == self.workflow.name) {
@@ -82,7 +128,7 @@ impl Event for WorkflowSelectedEvent {
                     None // Workflow not found in discovered workflows
                 }
             }
-            _ => None // Invalid transition - can only select from listed workflows
+            _ => None // Invalid transition - can only select from a fresh session or listed workflows
         }
     }
 
@@ -194,6 +240,148 @@ impl Event for WorkflowArgumentsResolvedEvent {
     }
 }
 
+impl Event for WorkflowExecutedEvent {
+    fn apply(&self, current_state: Option<&WorkflowState>) -> Option<WorkflowState> {
+        let default_state = WorkflowState::default();
+        let current = current_state.unwrap_or(&default_state);
+
+        match current {
+            WorkflowState::WorkflowArgumentsResolved(state) => {
+                Some(WorkflowState::WorkflowExecuted(WorkflowExecutedState {
+                    discovered_workflows: state.discovered_workflows.clone(),
+                    selected_workflow:    state.selected_workflow.clone(),
+                    execution_id:         state.execution_id.clone(),
+                    resolved_arguments:   state.resolved_arguments.clone(),
+                    command:              self.command.clone(),
+                    exit_code:            self.exit_code,
+                    duration_ms:          self.duration_ms
+                }))
+            }
+            _ => None // Invalid transition - can only execute a workflow with resolved arguments
+        }
+    }
+
+    fn event_type(&self) -> &'static str {
+        "workflow-executed"
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    fn event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    fn state_type(&self) -> &'static str {
+        "workflow-state"
+    }
+
+    fn clone_event(&self) -> Box<dyn Event> {
+        Box::new(self.clone())
+    }
+}
+
+impl Event for WorkflowExecutionFailedEvent {
+    fn apply(&self, current_state: Option<&WorkflowState>) -> Option<WorkflowState> {
+        let default_state = WorkflowState::default();
+        let current = current_state.unwrap_or(&default_state);
+
+        match current {
+            WorkflowState::WorkflowArgumentsResolved(state) => {
+                Some(WorkflowState::WorkflowExecutionFailed(WorkflowExecutionFailedState {
+                    discovered_workflows: state.discovered_workflows.clone(),
+                    selected_workflow:    state.selected_workflow.clone(),
+                    execution_id:         state.execution_id.clone(),
+                    resolved_arguments:   state.resolved_arguments.clone(),
+                    command:              self.command.clone(),
+                    exit_code:            self.exit_code,
+                    duration_ms:          self.duration_ms,
+                    error:                self.error.clone()
+                }))
+            }
+            _ => None // Invalid transition - can only execute a workflow with resolved arguments
+        }
+    }
+
+    fn event_type(&self) -> &'static str {
+        "workflow-execution-failed"
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    fn event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    fn state_type(&self) -> &'static str {
+        "workflow-state"
+    }
+
+    fn clone_event(&self) -> Box<dyn Event> {
+        Box::new(self.clone())
+    }
+}
+
+impl Event for WorkflowStepCompletedEvent {
+    fn apply(&self, current_state: Option<&WorkflowState>) -> Option<WorkflowState> {
+        let default_state = WorkflowState::default();
+        let current = current_state.unwrap_or(&default_state);
+
+        match current {
+            WorkflowState::WorkflowArgumentsResolved(state) => {
+                Some(WorkflowState::WorkflowStepCompleted(WorkflowStepCompletedState {
+                    discovered_workflows: state.discovered_workflows.clone(),
+                    selected_workflow:    state.selected_workflow.clone(),
+                    execution_id:         state.execution_id.clone(),
+                    resolved_arguments:   state.resolved_arguments.clone(),
+                    steps:                vec![self.step.clone()]
+                }))
+            }
+            WorkflowState::WorkflowStepCompleted(state) if !state.failed() => {
+                let mut next = state.clone();
+                next.steps.push(self.step.clone());
+                Some(WorkflowState::WorkflowStepCompleted(next))
+            }
+            _ => None // Invalid transition - steps run after argument resolution and stop at the first failure
+        }
+    }
+
+    fn event_type(&self) -> &'static str {
+        "workflow-step-completed"
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    fn event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    fn state_type(&self) -> &'static str {
+        "workflow-state"
+    }
+
+    fn clone_event(&self) -> Box<dyn Event> {
+        Box::new(self.clone())
+    }
+}
+
 impl Event for WorkflowCompletedEvent {
     fn apply(&self, current_state: Option<&WorkflowState>) -> Option<WorkflowState> {
         let default_state = WorkflowState::default();
@@ -208,7 +396,23 @@ impl Event for WorkflowCompletedEvent {
                     state.resolved_arguments.clone()
                 )))
             }
-            _ => None // Invalid transition - can only complete a workflow with resolved arguments
+            WorkflowState::WorkflowExecuted(state) => {
+                Some(WorkflowState::WorkflowCompleted(WorkflowCompletedState::new(
+                    state.discovered_workflows.clone(),
+                    state.selected_workflow.clone(),
+                    state.execution_id.clone(),
+                    state.resolved_arguments.clone()
+                )))
+            }
+            WorkflowState::WorkflowStepCompleted(state) if !state.failed() => {
+                Some(WorkflowState::WorkflowCompleted(WorkflowCompletedState::new(
+                    state.discovered_workflows.clone(),
+                    state.selected_workflow.clone(),
+                    state.execution_id.clone(),
+                    state.resolved_arguments.clone()
+                )))
+            }
+            _ => None // Invalid transition - can only complete a resolved or successfully executed workflow
         }
     }
 
@@ -417,9 +621,13 @@ macro_rules! impl_event {
 
 impl_event!(WorkflowEvent {
     WorkflowDiscovered(event),
+    WorkflowDiscoveryFailed(event),
     WorkflowSelected(event),
     WorkflowStarted(event),
     WorkflowArgumentsResolved(event),
+    WorkflowExecuted(event),
+    WorkflowExecutionFailed(event),
+    WorkflowStepCompleted(event),
     WorkflowCompleted(event),
     AvailableWorkflowsListed(event),
     SyncRequested(event),
@@ -434,7 +642,8 @@ impl Event for SyncRequestedEvent {
         Some(WorkflowState::SyncRequested(SyncRequestedState::new(
             self.remote_url.clone(),
             self.branch.clone(),
-            self.ssh_key.clone()
+            self.ssh_key.clone(),
+            self.remote.clone()
         )))
     }
 
@@ -501,7 +710,11 @@ mod tests {
     use uuid::Uuid;
 
     use crate::{
-        domain::{event::*, state::*, workflow::Workflow},
+        domain::{
+            event::*,
+            state::*,
+            workflow::{DiscoveryFailure, StepAction, StepRecord, Workflow}
+        },
         port::event::Event
     };
 
@@ -514,8 +727,12 @@ mod tests {
             source_url:  None,
             author:      None,
             author_url:  None,
+            execute:     false,
+            steps:       vec![],
             shells:      vec![],
-            tags:        vec![]
+            tags:        vec![],
+            namespace:   None,
+            origin:      None
         }
     }
 
@@ -550,7 +767,8 @@ mod tests {
         WorkflowState::SyncRequested(SyncRequestedState::new(
             "https://example.com/repo.git".to_string(),
             "main".to_string(),
-            None
+            None,
+            Some("vault".to_string())
         ))
     }
 
@@ -603,6 +821,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn discovered_keeps_same_name_in_another_namespace() {
+        let event = WorkflowDiscoveredEvent {
+            event_id:  Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            workflow:  Workflow { namespace: Some("k8s".to_string()), ..test_workflow() },
+            file_path: "k8s/test.yaml".to_string()
+        };
+        let result = event.apply(Some(&discovered_state())).unwrap();
+        match result {
+            WorkflowState::WorkflowsDiscovered(s) => assert_eq!(s.discovered_workflows.len(), 2),
+            _ => panic!("Expected WorkflowsDiscovered")
+        }
+    }
+
     #[test]
     fn discovered_from_invalid_state_returns_none() {
         let event = WorkflowDiscoveredEvent {
@@ -614,6 +847,26 @@ mod tests {
         assert!(event.apply(Some(&selected_state())).is_none());
     }
 
+    #[test]
+    fn discovery_failure_keeps_discovered_workflows() {
+        let event = WorkflowDiscoveryFailedEvent {
+            event_id:  Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            failure:   DiscoveryFailure {
+                path:    "bad.yaml".to_string(),
+                line:    Some(3),
+                column:  Some(1),
+                message: "did not find expected key".to_string()
+            }
+        };
+        match event.apply(Some(&discovered_state())).unwrap() {
+            WorkflowState::WorkflowsDiscovered(s) => assert_eq!(s.discovered_workflows.len(), 1),
+            _ => panic!("Expected WorkflowsDiscovered")
+        }
+        assert!(matches!(event.apply(None), Some(WorkflowState::Initial(_))));
+        assert!(event.apply(Some(&selected_state())).is_none());
+    }
+
     #[test]
     fn discovered_from_none_uses_default() {
         let event = WorkflowDiscoveredEvent {
@@ -655,6 +908,24 @@ mod tests {
         assert!(event.apply(Some(&discovered_state())).is_none());
     }
 
+    #[test]
+    fn selected_from_initial_for_file_workflows() {
+        let event = WorkflowSelectedEvent {
+            event_id:  Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            workflow:  test_workflow(),
+            user:      "tester".to_string()
+        };
+        let result = event.apply(Some(&WorkflowState::default())).unwrap();
+        match result {
+            WorkflowState::WorkflowSelected(s) => {
+                assert_eq!(s.selected_workflow.name, "test-workflow");
+                assert_eq!(s.discovered_workflows.len(), 1);
+            }
+            _ => panic!("Expected WorkflowSelected")
+        }
+    }
+
     #[test]
     fn selected_from_invalid_state_returns_none() {
         let event = WorkflowSelectedEvent {
@@ -663,7 +934,7 @@ mod tests {
             workflow:  test_workflow(),
             user:      "tester".to_string()
         };
-        assert!(event.apply(Some(&WorkflowState::default())).is_none());
+        assert!(event.apply(Some(&started_state())).is_none());
     }
 
     #[test]
@@ -704,7 +975,8 @@ mod tests {
         let event = WorkflowArgumentsResolvedEvent {
             event_id:  Uuid::new_v4().to_string(),
             timestamp: Utc::now(),
-            arguments: args.clone()
+            arguments: args.clone(),
+            context:   None
         };
         let result = event.apply(Some(&started_state())).unwrap();
         match result {
@@ -721,7 +993,8 @@ mod tests {
         let event = WorkflowArgumentsResolvedEvent {
             event_id:  Uuid::new_v4().to_string(),
             timestamp: Utc::now(),
-            arguments: HashMap::new()
+            arguments: HashMap::new(),
+            context:   None
         };
         assert!(event.apply(Some(&selected_state())).is_none());
     }
@@ -740,6 +1013,106 @@ mod tests {
         }
     }
 
+    fn executed_event(exit_code: i32) -> WorkflowExecutedEvent {
+        WorkflowExecutedEvent {
+            event_id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            command: "echo hello".to_string(),
+            exit_code,
+            duration_ms: 12
+        }
+    }
+
+    #[test]
+    fn executed_from_resolved() {
+        let result = executed_event(0).apply(Some(&resolved_state())).unwrap();
+        match result {
+            WorkflowState::WorkflowExecuted(s) => {
+                assert_eq!(s.command, "echo hello");
+                assert_eq!(s.exit_code, 0);
+                assert_eq!(s.duration_ms, 12);
+                assert_eq!(s.resolved_arguments.get("msg").unwrap(), "hello");
+            }
+            _ => panic!("Expected WorkflowExecuted")
+        }
+    }
+
+    #[test]
+    fn executed_from_invalid_state_returns_none() {
+        assert!(executed_event(0).apply(Some(&started_state())).is_none());
+    }
+
+    #[test]
+    fn execution_failed_from_resolved() {
+        let event = WorkflowExecutionFailedEvent {
+            event_id:    Uuid::new_v4().to_string(),
+            timestamp:   Utc::now(),
+            command:     "false".to_string(),
+            exit_code:   Some(1),
+            duration_ms: 3,
+            error:       None
+        };
+        match event.apply(Some(&resolved_state())).unwrap() {
+            WorkflowState::WorkflowExecutionFailed(s) => {
+                assert_eq!(s.exit_code, Some(1));
+                assert_eq!(s.command, "false");
+            }
+            _ => panic!("Expected WorkflowExecutionFailed")
+        }
+    }
+
+    fn step_event(index: usize, action: StepAction, exit_code: Option<i32>) -> WorkflowStepCompletedEvent {
+        WorkflowStepCompletedEvent {
+            event_id:  Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            step:      StepRecord {
+                index,
+                name: format!("step {}", index),
+                command: "echo hello".to_string(),
+                action,
+                exit_code,
+                duration_ms: exit_code.map(|_| 5),
+                error: None
+            }
+        }
+    }
+
+    #[test]
+    fn step_completed_accumulates_steps() {
+        let first = step_event(0, StepAction::Executed, Some(0)).apply(Some(&resolved_state())).unwrap();
+        let second = step_event(1, StepAction::Skipped, None).apply(Some(&first)).unwrap();
+        match second {
+            WorkflowState::WorkflowStepCompleted(s) => {
+                assert_eq!(s.steps.len(), 2);
+                assert_eq!(s.steps[1].action, StepAction::Skipped);
+                assert_eq!(s.resolved_arguments.get("msg").unwrap(), "hello");
+            }
+            _ => panic!("Expected WorkflowStepCompleted")
+        }
+    }
+
+    #[test]
+    fn no_step_or_completion_after_failed_step() {
+        let failed = step_event(0, StepAction::Executed, Some(2)).apply(Some(&resolved_state())).unwrap();
+        let completed = WorkflowCompletedEvent { event_id: Uuid::new_v4().to_string(), timestamp: Utc::now() };
+        assert!(step_event(1, StepAction::Copied, None).apply(Some(&failed)).is_none());
+        assert!(completed.apply(Some(&failed)).is_none());
+    }
+
+    #[test]
+    fn completed_from_step_completed() {
+        let steps = step_event(0, StepAction::Copied, None).apply(Some(&resolved_state())).unwrap();
+        let event = WorkflowCompletedEvent { event_id: Uuid::new_v4().to_string(), timestamp: Utc::now() };
+        assert!(matches!(event.apply(Some(&steps)), Some(WorkflowState::WorkflowCompleted(_))));
+    }
+
+    #[test]
+    fn completed_from_executed() {
+        let executed = executed_event(0).apply(Some(&resolved_state())).unwrap();
+        let event = WorkflowCompletedEvent { event_id: Uuid::new_v4().to_string(), timestamp: Utc::now() };
+        assert!(matches!(event.apply(Some(&executed)), Some(WorkflowState::WorkflowCompleted(_))));
+    }
+
     #[test]
     fn completed_from_invalid_state_returns_none() {
         let event = WorkflowCompletedEvent { event_id: Uuid::new_v4().to_string(), timestamp: Utc::now() };
@@ -791,13 +1164,15 @@ mod tests {
             timestamp:  Utc::now(),
             remote_url: "https://example.com/repo.git".to_string(),
             branch:     "main".to_string(),
-            ssh_key:    Some("/path/to/key".to_string())
+            ssh_key:    Some("/path/to/key".to_string()),
+            remote:     Some("vault".to_string())
         };
         let result = event.apply(Some(&WorkflowState::default())).unwrap();
         match result {
             WorkflowState::SyncRequested(s) => {
                 assert_eq!(s.remote_url, "https://example.com/repo.git");
                 assert_eq!(s.ssh_key, Some("/path/to/key".to_string()));
+                assert_eq!(s.remote.as_deref(), Some("vault"));
             }
             _ => panic!("Expected SyncRequested")
         }
@@ -913,10 +1288,39 @@ mod tests {
             "workflow-started"
         );
         assert_eq!(
-            WorkflowArgumentsResolvedEvent { event_id: id.clone(), timestamp: ts, arguments: HashMap::new() }
-                .event_type(),
+            WorkflowArgumentsResolvedEvent {
+                event_id:  id.clone(),
+                timestamp: ts,
+                arguments: HashMap::new(),
+                context:   None
+            }
+            .event_type(),
             "workflow-arguments-resolved"
         );
+        assert_eq!(
+            WorkflowExecutedEvent {
+                event_id:    id.clone(),
+                timestamp:   ts,
+                command:     "c".to_string(),
+                exit_code:   0,
+                duration_ms: 0
+            }
+            .event_type(),
+            "workflow-executed"
+        );
+        assert_eq!(
+            WorkflowExecutionFailedEvent {
+                event_id:    id.clone(),
+                timestamp:   ts,
+                command:     "c".to_string(),
+                exit_code:   None,
+                duration_ms: 0,
+                error:       None
+            }
+            .event_type(),
+            "workflow-execution-failed"
+        );
+        assert_eq!(step_event(0, StepAction::Copied, None).event_type(), "workflow-step-completed");
         assert_eq!(WorkflowCompletedEvent { event_id: id.clone(), timestamp: ts }.event_type(), "workflow-completed");
         assert_eq!(
             AvailableWorkflowsListedEvent { event_id: id.clone(), timestamp: ts, workflows: vec![] }.event_type(),
@@ -928,7 +1332,8 @@ mod tests {
                 timestamp:  ts,
                 remote_url: "r".to_string(),
                 branch:     "b".to_string(),
-                ssh_key:    None
+                ssh_key:    None,
+                remote:     None
             }
             .event_type(),
             "SyncRequested"
@@ -1,8 +1,44 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
 use async_trait::async_trait;
 use tokio::process::Command as TokioCommand;
 
 use crate::{domain::error::WorkflowError, port::executor::CommandExecutor, t_params};
 
+/// The user's login shell from `$SHELL`, falling back to `sh`
+pub fn user_shell() -> String {
+    std::env::var("SHELL").ok().filter(|shell| !shell.is_empty()).unwrap_or_else(|| "sh".to_string())
+}
+
+/// Process ID of the command `run_attached` is waiting for, 0 when none
+static ATTACHED_CHILD: AtomicU32 = AtomicU32::new(0);
+
+/// Pass an interrupt (Ctrl-C) on to the command running attached to the terminal, so it stops
+/// and its exit is recorded. Returns false when no command is running.
+pub fn interrupt_attached() -> bool {
+    let pid = ATTACHED_CHILD.load(Ordering::SeqCst);
+    if pid == 0 {
+        return false;
+    }
+
+    // Ctrl-C typed in the terminal already reached the command; a second SIGINT would make many
+    // tools abort instead of shutting down
+    #[cfg(unix)]
+    if !in_foreground() {
+        unsafe {
+            libc::kill(-(pid as libc::pid_t), libc::SIGINT);
+        }
+    }
+    true
+}
+
+/// Whether wf is in the terminal's foreground process group, so Ctrl-C reaches the commands it
+/// runs in that group as well
+#[cfg(unix)]
+fn in_foreground() -> bool {
+    unsafe { libc::tcgetpgrp(libc::STDIN_FILENO) == libc::getpgrp() }
+}
+
 /// Real implementation wrapping TokioCommand
 pub struct ShellExecutor;
 
@@ -30,6 +66,29 @@ impl CommandExecutor for ShellExecutor {
         String::from_utf8(output.stdout)
             .map_err(|e| WorkflowError::Execution(t_params!("error_failed_to_parse_command_output", &[&e.to_string()])))
     }
+
+    async fn run_attached(&self, command: &str) -> Result<Option<i32>, WorkflowError> {
+        let mut process = TokioCommand::new(user_shell());
+        process.arg("-c").arg(command);
+        // Without a terminal handing it Ctrl-C, the command gets a process group of its own for
+        // `interrupt_attached` to signal as a whole
+        #[cfg(unix)]
+        if !in_foreground() {
+            process.process_group(0);
+        }
+
+        let mut child = process
+            .spawn()
+            .map_err(|e| WorkflowError::Execution(t_params!("error_failed_to_execute_command", &[&e.to_string()])))?;
+
+        ATTACHED_CHILD.store(child.id().unwrap_or_default(), Ordering::SeqCst);
+        let status = child.wait().await;
+        ATTACHED_CHILD.store(0, Ordering::SeqCst);
+
+        let status = status
+            .map_err(|e| WorkflowError::Execution(t_params!("error_failed_to_execute_command", &[&e.to_string()])))?;
+        Ok(status.code())
+    }
 }
 
 #[cfg(test)]
@@ -59,6 +118,16 @@ pub mod mock {
                 None => Err(WorkflowError::Execution(format!("MockExecutor: no response configured for '{}'", command)))
             }
         }
+
+        /// Configured successes exit with 0, configured errors with 1
+        async fn run_attached(&self, command: &str) -> Result<Option<i32>, WorkflowError> {
+            let responses = self.responses.lock().unwrap();
+            match responses.get(command) {
+                Some(Ok(_)) => Ok(Some(0)),
+                Some(Err(_)) => Ok(Some(1)),
+                None => Err(WorkflowError::Execution(format!("MockExecutor: no response configured for '{}'", command)))
+            }
+        }
     }
 }
 
@@ -27,6 +27,10 @@ impl FileSystem for StdFileSystem {
         path.exists()
     }
 
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
     fn create_dir_all(&self, path: &Path) -> Result<(), WorkflowError> {
         std::fs::create_dir_all(path).map_err(|e| WorkflowError::from(StorageError::Io(e.to_string())))
     }
@@ -89,7 +93,14 @@ pub mod mock {
         fn exists(&self, path: &Path) -> bool {
             let files = self.files.lock().unwrap();
             let dirs = self.dirs.lock().unwrap();
-            files.contains_key(path) || dirs.contains(&path.to_path_buf())
+            files.keys().any(|p| p.starts_with(path)) || dirs.contains(&path.to_path_buf())
+        }
+
+        /// Created directories and the parents of stored files
+        fn is_dir(&self, path: &Path) -> bool {
+            let files = self.files.lock().unwrap();
+            let dirs = self.dirs.lock().unwrap();
+            dirs.iter().any(|d| d == path) || files.keys().any(|p| p != path && p.starts_with(path))
         }
 
         fn create_dir_all(&self, path: &Path) -> Result<(), WorkflowError> {
@@ -116,7 +127,15 @@ pub mod mock {
 
         fn read_dir_entries(&self, path: &Path) -> Result<Vec<PathBuf>, WorkflowError> {
             let files = self.files.lock().unwrap();
-            let entries: Vec<PathBuf> = files.keys().filter(|p| p.parent() == Some(path)).cloned().collect();
+            let dirs = self.dirs.lock().unwrap();
+            let mut entries: Vec<PathBuf> = files
+                .keys()
+                .chain(dirs.iter())
+                .filter_map(|p| p.ancestors().find(|a| a.parent() == Some(path)))
+                .map(Path::to_path_buf)
+                .collect();
+            entries.sort();
+            entries.dedup();
             Ok(entries)
         }
     }
@@ -185,6 +204,24 @@ mod tests {
         assert_eq!(entries.len(), 2);
     }
 
+    #[test]
+    fn mock_fs_lists_and_recognizes_subdirectories() {
+        let fs = MockFileSystem::new();
+        fs.write(Path::new("/repo/app.yaml"), "a").unwrap();
+        fs.write(Path::new("/repo/k8s/base/deploy.yaml"), "d").unwrap();
+        fs.create_dir_all(Path::new("/repo/empty")).unwrap();
+
+        let entries = fs.read_dir_entries(Path::new("/repo")).unwrap();
+        assert_eq!(
+            entries,
+            vec![PathBuf::from("/repo/app.yaml"), PathBuf::from("/repo/empty"), PathBuf::from("/repo/k8s")]
+        );
+        assert!(fs.is_dir(Path::new("/repo/k8s")));
+        assert!(fs.is_dir(Path::new("/repo/empty")));
+        assert!(!fs.is_dir(Path::new("/repo/app.yaml")));
+        assert!(fs.exists(Path::new("/repo/k8s/base")));
+    }
+
     #[test]
     fn mock_fs_with_files_constructor() {
         let mut files = std::collections::HashMap::new();
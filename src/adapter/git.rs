@@ -97,6 +97,29 @@ impl GitClient for Git2Client {
             }
         };
 
+        let source = match &options.path {
+            Some(path) => temp_dir.join(path),
+            None => temp_dir.clone()
+        };
+        if !source.is_dir() {
+            let _ = fs::remove_dir_all(&temp_dir);
+            let path = options.path.as_deref().unwrap_or_default();
+            return Err(WorkflowError::Network(t_params!("git_path_not_found", &[path, url])));
+        }
+        // `path` comes from settings: don't follow it, or a link in the repository, out of the clone
+        let inside = match (source.canonicalize(), temp_dir.canonicalize()) {
+            (Ok(source), Ok(root)) => source.starts_with(root),
+            _ => false
+        };
+        if !inside {
+            let _ = fs::remove_dir_all(&temp_dir);
+            let path = options.path.as_deref().unwrap_or_default();
+            return Err(WorkflowError::from(ValidationError::Other(t_params!(
+                "git_path_outside_repository",
+                &[path, url]
+            ))));
+        }
+
         // Clone succeeded — now make destination ready and move files in.
         if destination.exists() {
             if let Ok(entries) = fs::read_dir(destination) {
@@ -125,9 +148,9 @@ impl GitClient for Git2Client {
                 .map_err(|e| WorkflowError::from(StorageError::Io(e.to_string())))?;
         }
 
-        // Move workflow files from the cloned repository to the destination directory.
-        // Skip the .git directory and any other hidden files.
-        if let Ok(entries) = fs::read_dir(&temp_dir) {
+        // Move workflow files from the cloned repository (or its `path`) to the destination
+        // directory. Skip the .git directory and any other hidden files.
+        if let Ok(entries) = fs::read_dir(&source) {
             for entry in entries.flatten() {
                 let path = entry.path();
                 let file_name = path.file_name().unwrap_or_default();
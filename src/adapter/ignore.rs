@@ -0,0 +1,127 @@
+//! `.wfignore` rules for workflow discovery
+//!
+//! One glob per line, relative to the workflows directory, in the spirit of `.gitignore`:
+//! - Blank lines and lines starting with `#` are skipped
+//! - A pattern without `/` matches a file or folder name at any depth (`*.draft.yaml`, `scratch`)
+//! - A pattern with `/` matches the whole relative path (`aws/legacy`, `/notes.yaml`)
+//! - A trailing `/` only matches folders; an ignored folder is not entered at all
+//! - A leading `!` brings back what an earlier pattern ignored; the last matching line wins
+
+use std::path::Path;
+
+use globset::{Glob, GlobMatcher};
+
+use crate::{
+    domain::error::{ValidationError, WorkflowError},
+    port::filesystem::FileSystem,
+    t_params
+};
+
+/// Name of the ignore file, read from the root of the workflows directory
+pub const IGNORE_FILE: &str = ".wfignore";
+
+struct IgnoreRule {
+    matcher:  GlobMatcher,
+    /// Pattern has a `/` inside, so it matches the relative path rather than a name
+    anchored: bool,
+    dir_only: bool,
+    negated:  bool
+}
+
+/// Parsed `.wfignore`; empty when there is none
+#[derive(Default)]
+pub struct IgnoreRules {
+    rules: Vec<IgnoreRule>
+}
+
+impl IgnoreRules {
+    /// Read `.wfignore` from `dir`, if there is one
+    pub fn load(fs: &dyn FileSystem, dir: &Path) -> Result<Self, WorkflowError> {
+        let path = dir.join(IGNORE_FILE);
+        if !fs.exists(&path) {
+            return Ok(Self::default());
+        }
+        Self::parse(&fs.read_to_string(&path)?)
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, WorkflowError> {
+        let mut rules = Vec::new();
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (negated, pattern) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line)
+            };
+            let (dir_only, pattern) = match pattern.strip_suffix('/') {
+                Some(rest) => (true, rest),
+                None => (false, pattern)
+            };
+            let anchored = pattern.contains('/');
+            let pattern = pattern.trim_start_matches('/');
+
+            let matcher = Glob::new(pattern)
+                .map_err(|e| {
+                    ValidationError::Other(t_params!("error_invalid_ignore_pattern", &[line, &e.to_string()]))
+                })?
+                .compile_matcher();
+            rules.push(IgnoreRule { matcher, anchored, dir_only, negated });
+        }
+        Ok(Self { rules })
+    }
+
+    /// Whether `relative` (a path inside the workflows directory) is ignored
+    pub fn is_ignored(&self, relative: &Path, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let matches = if rule.anchored {
+                rule.matcher.is_match(relative)
+            } else {
+                relative.file_name().is_some_and(|name| rule.matcher.is_match(name))
+            };
+            if matches {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_match_at_any_depth_and_paths_from_the_root() {
+        let rules = IgnoreRules::parse("# drafts\n*.draft.yaml\n\n/aws/legacy\n").unwrap();
+        assert!(rules.is_ignored(Path::new("deploy.draft.yaml"), false));
+        assert!(rules.is_ignored(Path::new("k8s/logs.draft.yaml"), false));
+        assert!(rules.is_ignored(Path::new("aws/legacy"), true));
+        assert!(!rules.is_ignored(Path::new("k8s/aws/legacy"), true));
+        assert!(!rules.is_ignored(Path::new("k8s/logs.yaml"), false));
+    }
+
+    #[test]
+    fn trailing_slash_only_matches_folders() {
+        let rules = IgnoreRules::parse("scratch/").unwrap();
+        assert!(rules.is_ignored(Path::new("scratch"), true));
+        assert!(!rules.is_ignored(Path::new("scratch"), false));
+    }
+
+    #[test]
+    fn negation_brings_files_back() {
+        let rules = IgnoreRules::parse("*.yaml\n!keep.yaml").unwrap();
+        assert!(rules.is_ignored(Path::new("drop.yaml"), false));
+        assert!(!rules.is_ignored(Path::new("db/keep.yaml"), false));
+    }
+
+    #[test]
+    fn invalid_pattern_is_an_error() {
+        assert!(IgnoreRules::parse("[unclosed").is_err());
+    }
+}
@@ -405,7 +405,8 @@ mod tests {
             timestamp:  Utc::now(),
             remote_url: "test-url".to_string(),
             branch:     "main".to_string(),
-            ssh_key:    None
+            ssh_key:    None,
+            remote:     None
         })];
 
         journal.persist_events(session_id, &test_events).await.unwrap();
@@ -450,7 +451,11 @@ mod tests {
                 source_url:  None,
                 author:      None,
                 author_url:  None,
-                shells:      vec![]
+                execute:     false,
+                steps:       vec![],
+                shells:      vec![],
+                namespace:   None,
+                origin:      None
             },
             file_path: format!("{}.yaml", name)
         })
@@ -0,0 +1,575 @@
+//! Static checks behind `wf lint`
+//!
+//! Catches at once what would otherwise only fail while running a workflow, or never:
+//! - Files that don't load (YAML errors, broken `extends` or `include_arguments`) and fields the
+//!   format doesn't know
+//! - Template and `when` syntax errors, and variables without a matching argument
+//! - Placeholders wrapped in quotes of their own although their value is quoted already
+//! - Arguments declared twice, reserved or never used
+//! - Unknown or cyclic dependencies, `dynamic_resolution` pointing below the argument
+//! - Enum arguments without options and impossible `min_selections`/`max_selections`
+//! - Computed arguments without expression, broken `validation:` patterns and defaults they reject
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf}
+};
+
+use crate::{
+    adapter::{
+        command::template::{TeraInspector, placeholders, template_variables},
+        loader::{compose_workflow, find_workflow_files, parse_workflow},
+        schema::{unknown_fields, workflow_schema}
+    },
+    domain::{
+        error::{ValidationError, WorkflowError},
+        lint::{LintIssue, LintReport, LintSeverity},
+        workflow::{
+            ArgumentType, ArgumentValidation, QuoteStyle, RESERVED_ARGUMENT_NAMES, Workflow, WorkflowArgument,
+            resolution_order
+        }
+    },
+    port::filesystem::FileSystem,
+    t_params
+};
+
+/// Lint every workflow file among `paths`; directories stand for the workflow files in them
+/// and their subfolders, as discovery finds them
+pub fn lint_paths(fs: &dyn FileSystem, paths: &[PathBuf]) -> Result<LintReport, WorkflowError> {
+    let mut files = Vec::new();
+    for path in paths {
+        if fs.is_dir(path) {
+            files.extend(find_workflow_files(fs, path)?);
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    let issues = files.iter().flat_map(|file| lint_file(fs, file)).collect();
+    Ok(LintReport { files: files.len(), issues })
+}
+
+/// Lint one workflow file, reporting it as a whole when it can't be loaded
+pub fn lint_file(fs: &dyn FileSystem, path: &Path) -> Vec<LintIssue> {
+    match parse_workflow(fs, path) {
+        Ok(workflow) => {
+            let shown = path.display().to_string();
+            let mut issues = lint_workflow(&shown, &workflow);
+            if let Ok(composed) = compose_workflow(fs, path) {
+                let mut lint = Lint { path: &shown, workflow: &workflow, issues: Vec::new() };
+                for field in unknown_fields(&workflow_schema(), &composed) {
+                    lint.report(
+                        LintSeverity::Warning,
+                        "unknown-field",
+                        None,
+                        t_params!("lint_unknown_field", &[&field])
+                    );
+                }
+                issues.splice(0..0, lint.issues);
+            }
+            issues
+        }
+        Err(failure) => vec![LintIssue {
+            path:     failure.path,
+            line:     failure.line,
+            column:   failure.column,
+            workflow: None,
+            argument: None,
+            severity: LintSeverity::Error,
+            code:     "invalid-file".to_string(),
+            message:  failure.message
+        }]
+    }
+}
+
+/// Findings for a loaded workflow, `path` being the file it came from
+pub fn lint_workflow(path: &str, workflow: &Workflow) -> Vec<LintIssue> {
+    let mut lint = Lint { path, workflow, issues: Vec::new() };
+    lint.argument_names();
+    lint.dependencies();
+    lint.templates();
+    lint.quoted_placeholders();
+    for arg in &workflow.arguments {
+        lint.argument(arg);
+    }
+    lint.issues
+}
+
+struct Lint<'a> {
+    path:     &'a str,
+    workflow: &'a Workflow,
+    issues:   Vec<LintIssue>
+}
+
+impl Lint<'_> {
+    fn report(&mut self, severity: LintSeverity, code: &str, argument: Option<&str>, message: String) {
+        self.issues.push(LintIssue {
+            path: self.path.to_string(),
+            line: None,
+            column: None,
+            workflow: Some(self.workflow.name.clone()),
+            argument: argument.map(str::to_string),
+            severity,
+            code: code.to_string(),
+            message
+        });
+    }
+
+    fn argument_names(&mut self) {
+        let mut seen = HashSet::new();
+        for arg in &self.workflow.arguments {
+            let name = arg.name.as_str();
+            if !seen.insert(name) {
+                self.report(
+                    LintSeverity::Error,
+                    "duplicate-argument",
+                    Some(name),
+                    t_params!("lint_duplicate_argument", &[name])
+                );
+            }
+            if RESERVED_ARGUMENT_NAMES.contains(&name) {
+                let message = ValidationError::ReservedArgumentName(name.to_string()).to_string();
+                self.report(LintSeverity::Error, "reserved-argument-name", Some(name), message);
+            }
+        }
+    }
+
+    /// Unknown dependencies, cycles, and `dynamic_resolution` on an argument declared later
+    fn dependencies(&mut self) {
+        let arguments = &self.workflow.arguments;
+        let position: HashMap<&str, usize> =
+            arguments.iter().enumerate().map(|(index, arg)| (arg.name.as_str(), index)).collect();
+
+        let mut unknown = false;
+        for (index, arg) in arguments.iter().enumerate() {
+            for dependency in arg.dependencies() {
+                if !position.contains_key(dependency) {
+                    unknown = true;
+                    let message = ValidationError::UnknownDependency(arg.name.clone(), dependency.to_string());
+                    self.report(LintSeverity::Error, "unknown-dependency", Some(&arg.name), message.to_string());
+                }
+            }
+            if let Some(dependency) = &arg.dynamic_resolution
+                && position.get(dependency.as_str()).is_some_and(|&at| at > index)
+            {
+                self.report(
+                    LintSeverity::Warning,
+                    "late-dynamic-resolution",
+                    Some(&arg.name),
+                    t_params!("lint_late_dynamic_resolution", &[&arg.name, dependency])
+                );
+            }
+        }
+
+        // With unknown dependencies there is no order to look for cycles in
+        if !unknown && let Err(e) = resolution_order(arguments, &TeraInspector) {
+            self.report(LintSeverity::Error, "dependency-cycle", None, e.to_string());
+        }
+    }
+
+    /// Syntax of every template, placeholders without argument, and arguments no template uses
+    fn templates(&mut self) {
+        let workflow = self.workflow;
+        let mut templates: Vec<(String, Option<&str>, &str)> = Vec::new();
+        if !workflow.is_multi_step() || !workflow.command.is_empty() {
+            templates.push(("command".to_string(), None, &workflow.command));
+        }
+        for (index, step) in workflow.steps.iter().enumerate() {
+            templates.push((format!("steps[{}].command", index), None, &step.command));
+        }
+        for arg in &workflow.arguments {
+            let fields = [("enum_command", &arg.enum_command), ("expression", &arg.expression)];
+            for (field, template) in fields {
+                if let Some(template) = template {
+                    templates.push((format!("arguments.{}.{}", arg.name, field), Some(&arg.name), template));
+                }
+            }
+        }
+        // A condition is an expression, checked the way it is evaluated: inside `{% if %}`
+        let conditions: Vec<(&WorkflowArgument, String)> = workflow
+            .arguments
+            .iter()
+            .filter_map(|arg| {
+                arg.when.as_ref().map(|condition| (arg, format!("{{% if {} %}}{{% endif %}}", condition)))
+            })
+            .collect();
+        for (arg, condition) in &conditions {
+            templates.push((format!("arguments.{}.when", arg.name), Some(&arg.name), condition));
+        }
+
+        let names: HashSet<&str> = workflow.arguments.iter().map(|arg| arg.name.as_str()).collect();
+        let mut used: HashSet<String> = HashSet::new();
+
+        for (location, owner, template) in &templates {
+            let variables = match template_variables(template) {
+                Ok(variables) => variables,
+                Err(e) => {
+                    self.report(
+                        LintSeverity::Error,
+                        "template-syntax",
+                        *owner,
+                        t_params!("lint_template_syntax", &[location, &e])
+                    );
+                    continue;
+                }
+            };
+
+            let mut undefined: Vec<&String> = variables
+                .iter()
+                .filter(|variable| {
+                    !names.contains(variable.as_str()) && !RESERVED_ARGUMENT_NAMES.contains(&variable.as_str())
+                })
+                .collect();
+            undefined.sort();
+            for variable in undefined {
+                self.report(
+                    LintSeverity::Error,
+                    "undefined-placeholder",
+                    *owner,
+                    t_params!("lint_undefined_placeholder", &[location, variable])
+                );
+            }
+
+            used.extend(variables.into_iter().filter(|variable| Some(variable.as_str()) != *owner));
+        }
+
+        for arg in &workflow.arguments {
+            if !used.contains(&arg.name) {
+                self.report(
+                    LintSeverity::Warning,
+                    "unused-argument",
+                    Some(&arg.name),
+                    t_params!("lint_unused_argument", &[&arg.name])
+                );
+            }
+        }
+    }
+
+    /// `"{{ name }}"` where `name` is quoted on its own: it still renders right, escaped for the
+    /// template's quotes, but the quotes are redundant
+    fn quoted_placeholders(&mut self) {
+        let workflow = self.workflow;
+        let templates = std::iter::once(&workflow.command)
+            .chain(workflow.steps.iter().map(|step| &step.command))
+            .chain(workflow.arguments.iter().filter_map(|arg| arg.enum_command.as_ref()));
+
+        let mut reported = HashSet::new();
+        for template in templates {
+            for placeholder in placeholders(template) {
+                let quoted = workflow
+                    .arguments
+                    .iter()
+                    .any(|arg| arg.name == placeholder.name && arg.quote_style() != QuoteStyle::Raw);
+                if quoted && placeholder.wrapped && reported.insert(placeholder.name.clone()) {
+                    self.report(
+                        LintSeverity::Warning,
+                        "quoted-placeholder",
+                        Some(&placeholder.name),
+                        t_params!("lint_quoted_placeholder", &[&placeholder.name])
+                    );
+                }
+            }
+        }
+    }
+
+    fn argument(&mut self, arg: &WorkflowArgument) {
+        let name = Some(arg.name.as_str());
+        let variants = arg.enum_variants.as_ref().map_or(0, Vec::len);
+
+        if arg.arg_type == ArgumentType::Enum && variants == 0 && arg.enum_command.is_none() {
+            let message = ValidationError::EnumMissingConfig(arg.name.clone()).to_string();
+            self.report(LintSeverity::Error, "enum-missing-options", name, message);
+        }
+        if arg.arg_type == ArgumentType::Computed && arg.expression.is_none() {
+            let message = ValidationError::ComputedMissingExpression(arg.name.clone()).to_string();
+            self.report(LintSeverity::Error, "computed-missing-expression", name, message);
+        }
+
+        self.selections(arg, variants);
+
+        if let Some(Err(e)) = arg.validation.as_ref().map(ArgumentValidation::regex) {
+            self.report(LintSeverity::Error, "invalid-validation-pattern", name, e);
+        } else if let Some(default) = &arg.default_value
+            && let Err(e) = arg.check_value(default)
+        {
+            self.report(
+                LintSeverity::Warning,
+                "invalid-default-value",
+                name,
+                t_params!("lint_invalid_default_value", &[&arg.name, &e])
+            );
+        }
+    }
+
+    /// `min_selections`/`max_selections` that no selection can satisfy, or that have no effect
+    fn selections(&mut self, arg: &WorkflowArgument, variants: usize) {
+        let name = Some(arg.name.as_str());
+        let (min, max) = (arg.min_selections, arg.max_selections);
+        if min.is_none() && max.is_none() {
+            return;
+        }
+
+        if !arg.multi {
+            self.report(
+                LintSeverity::Warning,
+                "selections-without-multi",
+                name,
+                t_params!("lint_selections_without_multi", &[&arg.name])
+            );
+        }
+        if max == Some(0) {
+            self.report(
+                LintSeverity::Error,
+                "invalid-selections",
+                name,
+                t_params!("lint_selections_max_zero", &[&arg.name])
+            );
+        }
+        if let (Some(min), Some(max)) = (min, max)
+            && min > max
+        {
+            self.report(
+                LintSeverity::Error,
+                "invalid-selections",
+                name,
+                t_params!("lint_selections_min_above_max", &[&arg.name, &min.to_string(), &max.to_string()])
+            );
+        }
+        // Only static variants can be counted up front
+        if let Some(min) = min
+            && arg.enum_command.is_none()
+            && variants > 0
+            && min > variants
+        {
+            self.report(
+                LintSeverity::Error,
+                "invalid-selections",
+                name,
+                t_params!("lint_selections_above_variants", &[&arg.name, &min.to_string(), &variants.to_string()])
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::filesystem::mock::MockFileSystem;
+
+    fn lint(yaml: &str) -> Vec<LintIssue> {
+        let workflow: Workflow = serde_yaml::from_str(yaml).unwrap();
+        lint_workflow("w.yaml", &workflow)
+    }
+
+    fn codes(issues: &[LintIssue]) -> Vec<&str> {
+        issues.iter().map(|issue| issue.code.as_str()).collect()
+    }
+
+    #[test]
+    fn clean_workflow_has_no_findings() {
+        let issues = lint(
+            r#"
+name: logs
+description: Logs
+command: kubectl logs -n {{ namespace }} {{ ctx.user }}
+tags: []
+shells: []
+arguments:
+  - { name: namespace, description: "", arg_type: Enum, enum_variants: [a, b] }
+"#
+        );
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn placeholders_and_arguments_must_match() {
+        let issues = lint(
+            r#"
+name: x
+description: x
+command: echo {{ namespace }} {{ typo }}
+tags: []
+shells: []
+arguments:
+  - { name: namespace, description: "" }
+  - { name: unused, description: "" }
+  - { name: flag, arg_type: Boolean, description: "" }
+  - { name: extra, description: "", when: "flag" }
+"#
+        );
+        assert_eq!(codes(&issues), vec!["undefined-placeholder", "unused-argument", "unused-argument"]);
+        assert_eq!(issues[1].argument.as_deref(), Some("unused"));
+        assert_eq!(issues[2].argument.as_deref(), Some("extra"));
+    }
+
+    #[test]
+    fn quoted_placeholders_of_quoted_arguments_are_warnings() {
+        let issues = lint(
+            r#"
+name: x
+description: x
+command: git commit -m "{{ msg }}" -m '{{ body }}' --author "{{ author }}" {{ msg }}
+tags: []
+shells: []
+arguments:
+  - { name: msg, description: "" }
+  - { name: body, description: "" }
+  - { name: author, description: "", quote: none }
+"#
+        );
+        assert_eq!(codes(&issues), vec!["quoted-placeholder", "quoted-placeholder"]);
+        assert_eq!(issues[0].argument.as_deref(), Some("msg"));
+        assert_eq!(issues[1].argument.as_deref(), Some("body"));
+    }
+
+    #[test]
+    fn template_syntax_errors_are_reported() {
+        let issues = lint(
+            r#"
+name: x
+description: x
+tags: []
+shells: []
+arguments: []
+steps:
+  - { name: one, command: "echo {{ broken" }
+"#
+        );
+        assert_eq!(codes(&issues), vec!["template-syntax"]);
+        assert_eq!(issues[0].severity, LintSeverity::Error);
+    }
+
+    #[test]
+    fn conditions_are_checked_like_templates() {
+        let issues = lint(
+            r#"
+name: x
+description: x
+command: echo {{ action }} {{ replicas }} {{ force }}
+tags: []
+shells: []
+arguments:
+  - { name: action, description: "" }
+  - { name: replicas, description: "", when: 'acton == "scale"' }
+  - { name: force, description: "", when: "action ==" }
+"#
+        );
+        assert_eq!(codes(&issues), vec!["undefined-placeholder", "template-syntax"]);
+        assert_eq!(issues[0].argument.as_deref(), Some("replicas"));
+        assert_eq!(issues[1].argument.as_deref(), Some("force"));
+    }
+
+    #[test]
+    fn argument_definitions_are_checked() {
+        let issues = lint(
+            r#"
+name: x
+description: x
+command: "{{ env_name }} {{ pods }} {{ port }} {{ env_name }}"
+tags: []
+shells: []
+arguments:
+  - { name: env_name, description: "", arg_type: Enum }
+  - { name: env_name, description: "", enum_variants: [a] }
+  - name: pods
+    description: ""
+    arg_type: Enum
+    enum_variants: [a, b]
+    multi: true
+    min_selections: 3
+    max_selections: 2
+  - { name: port, description: "", arg_type: Number, default_value: "http" }
+"#
+        );
+        assert_eq!(
+            codes(&issues),
+            vec![
+                "duplicate-argument",
+                "enum-missing-options",
+                "invalid-selections",
+                "invalid-selections",
+                "invalid-default-value"
+            ]
+        );
+    }
+
+    #[test]
+    fn dependencies_are_checked() {
+        let issues = lint(
+            r#"
+name: x
+description: x
+command: "{{ a }} {{ b }} {{ c }}"
+tags: []
+shells: []
+arguments:
+  - { name: a, description: "", dynamic_resolution: b }
+  - { name: b, description: "", depends_on: [nope] }
+  - { name: c, description: "" }
+"#
+        );
+        assert_eq!(codes(&issues), vec!["late-dynamic-resolution", "unknown-dependency"]);
+
+        let issues = lint(
+            r#"
+name: x
+description: x
+command: "{{ a }} {{ b }}"
+tags: []
+shells: []
+arguments:
+  - { name: a, description: "", depends_on: [b] }
+  - { name: b, description: "", depends_on: [a] }
+"#
+        );
+        assert_eq!(codes(&issues), vec!["dependency-cycle"]);
+    }
+
+    #[test]
+    fn files_that_do_not_load_are_reported_with_position() {
+        let fs = MockFileSystem::new();
+        fs.write(Path::new("/w/bad.yaml"), "name: bad\ndescription: [x\ncommand: echo").unwrap();
+        fs.write(Path::new("/w/_base.yaml"), "description: base\ntags: []\nshells: []\narguments: []").unwrap();
+        fs.write(Path::new("/w/ok.yaml"), "extends: _base.yaml\nname: ok\ncommand: echo ok").unwrap();
+        fs.write(Path::new("/w/notes.md"), "# notes").unwrap();
+
+        let report = lint_paths(&fs, &[PathBuf::from("/w")]).unwrap();
+        assert_eq!(report.files, 2);
+        assert_eq!(codes(&report.issues), vec!["invalid-file"]);
+        assert!(report.issues[0].line.is_some());
+        assert!(report.failed(false));
+    }
+
+    #[test]
+    fn unknown_fields_are_warnings() {
+        let fs = MockFileSystem::new();
+        let path = Path::new("/w/typo.yaml");
+        fs.write(path, "name: x\ndescription: x\ncommand: echo\ntags: []\nshells: []\narguments: []\nexeucte: true")
+            .unwrap();
+
+        let issues = lint_file(&fs, path);
+        assert_eq!(codes(&issues), vec!["unknown-field"]);
+        assert_eq!(issues[0].severity, LintSeverity::Warning);
+    }
+
+    #[test]
+    fn warnings_only_fail_when_strict() {
+        let report = LintReport {
+            files:  1,
+            issues: lint(
+                r#"
+name: x
+description: x
+command: echo
+tags: []
+shells: []
+arguments:
+  - { name: unused, description: "" }
+"#
+            )
+        };
+        assert_eq!((report.errors(), report.warnings()), (0, 1));
+        assert!(!report.failed(false));
+        assert!(report.failed(true));
+    }
+}
@@ -0,0 +1,634 @@
+//! Loading workflow files
+//!
+//! A workflow may build on another file with `extends: _base.yaml` and pull shared argument
+//! definitions in with `include_arguments: [k8s/namespace]`. Both are merged into the YAML
+//! before it is parsed, so the rest of the pipeline only sees flattened workflows.
+
+use std::{
+    collections::HashSet,
+    path::{Component, Path, PathBuf}
+};
+
+use serde_yaml::{Location, Mapping, Value};
+
+use crate::{
+    adapter::{
+        command::template::TeraInspector,
+        ignore::IgnoreRules,
+        schema::{unknown_fields, workflow_schema}
+    },
+    domain::{
+        error::{StorageError, ValidationError, WorkflowError},
+        workflow::{DISCOVERY_FIELDS, DiscoveryFailure, Workflow, WorkflowOrigin, resolution_order}
+    },
+    port::filesystem::FileSystem,
+    t, t_params
+};
+
+/// Project-local workflow directories, looked up in the working directory and every directory above
+/// it
+pub const LOCAL_WORKFLOW_DIRS: [&str; 2] = [".wf", ".workflows"];
+
+/// Directory holding shared argument definitions, looked up next to a workflow file and in every
+/// directory above it
+pub const ARGUMENT_FRAGMENTS_DIR: &str = "_arguments";
+
+/// Whether a path looks like a workflow definition (`.yaml` or `.yml`)
+pub fn is_workflow_file(path: &Path) -> bool {
+    matches!(path.extension().and_then(|e| e.to_str()), Some("yaml") | Some("yml"))
+}
+
+/// Workflow files in `dir` and its subfolders, sorted. Skips what `.wfignore` lists, folders
+/// starting with `_` (shared definitions such as `_arguments`) or `.` (such as `.git`), and
+/// bases: files marked `abstract: true`, and files starting with `_` that another one `extends`.
+pub fn find_workflow_files(fs: &dyn FileSystem, dir: &Path) -> Result<Vec<PathBuf>, WorkflowError> {
+    let rules = IgnoreRules::load(fs, dir)?;
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        for entry in fs.read_dir_entries(&current)? {
+            let relative = entry.strip_prefix(dir).unwrap_or(&entry);
+            if fs.is_dir(&entry) {
+                let hidden = entry
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with('_') || name.starts_with('.'));
+                if !hidden && !rules.is_ignored(relative, true) {
+                    pending.push(entry);
+                }
+            } else if is_workflow_file(&entry) && !rules.is_ignored(relative, false) {
+                files.push(entry);
+            }
+        }
+    }
+
+    let documents: Vec<Value> = files.iter().map(|path| read_yaml(fs, path).unwrap_or_default()).collect();
+    let extended: HashSet<PathBuf> = files
+        .iter()
+        .zip(&documents)
+        .filter_map(|(path, yaml)| {
+            let base = yaml.get("extends")?.as_str()?;
+            Some(normalize(&path.parent().unwrap_or(Path::new("")).join(base)))
+        })
+        .collect();
+    let is_base = |path: &PathBuf, yaml: &Value| {
+        let underscored = path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with('_'));
+        yaml.get("abstract").and_then(Value::as_bool) == Some(true) || (underscored && extended.contains(path))
+    };
+
+    let mut files: Vec<PathBuf> =
+        files.into_iter().zip(&documents).filter(|(path, yaml)| !is_base(path, yaml)).map(|(path, _)| path).collect();
+    files.sort();
+    Ok(files)
+}
+
+/// The YAML of `path` as written, if it reads. Errors are left for loading the file to report.
+fn read_yaml(fs: &dyn FileSystem, path: &Path) -> Option<Value> {
+    serde_yaml::from_str(&fs.read_to_string(path).ok()?).ok()
+}
+
+/// `path` with `.` and `..` worked out, without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            other => normalized.push(other)
+        }
+    }
+    normalized
+}
+
+/// Directories discovery looks in, nearest first: `.wf/` and `.workflows/` in `working_directory`
+/// and each directory above it, then `global_dir`, then `workflow_path` (from `WF_PATH`).
+/// Directories that don't exist are left out.
+pub fn workflow_search_path(
+    fs: &dyn FileSystem,
+    working_directory: &Path,
+    global_dir: &Path,
+    workflow_path: &[PathBuf]
+) -> Vec<WorkflowOrigin> {
+    let mut search_path: Vec<WorkflowOrigin> = Vec::new();
+
+    for (depth, dir) in working_directory.ancestors().enumerate() {
+        for name in LOCAL_WORKFLOW_DIRS {
+            let label = match depth {
+                0 => format!("./{}", name),
+                _ => format!("{}{}", "../".repeat(depth), name)
+            };
+            search_path.push(WorkflowOrigin { dir: dir.join(name), label });
+        }
+    }
+    search_path.push(WorkflowOrigin { dir: global_dir.to_path_buf(), label: t!("workflow_origin_global") });
+    for dir in workflow_path {
+        search_path.push(WorkflowOrigin { dir: dir.clone(), label: dir.display().to_string() });
+    }
+
+    let mut seen = Vec::new();
+    search_path.retain(|origin| {
+        let keep = fs.is_dir(&origin.dir) && !seen.contains(&origin.dir);
+        seen.push(origin.dir.clone());
+        keep
+    });
+    search_path
+}
+
+/// Namespace of a workflow file: its folder relative to `root` (e.g. `k8s` for `k8s/logs.yaml`),
+/// none at the top level
+pub fn namespace_of(root: &Path, path: &Path) -> Option<String> {
+    let folder = path.strip_prefix(root).ok()?.parent()?;
+    let parts: Vec<String> = folder.components().map(|part| part.as_os_str().to_string_lossy().into_owned()).collect();
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// Read and parse a single workflow YAML file using the FileSystem trait, with its `extends` base
+/// and `include_arguments` merged in.
+/// Unknown argument dependencies and dependency cycles are reported here, before any prompt.
+/// With `strict`, so are fields the format doesn't know, which are otherwise ignored.
+pub fn load_workflow(fs: &dyn FileSystem, path: &Path, strict: bool) -> Result<Workflow, WorkflowError> {
+    load(fs, path, strict).map_err(|e| e.error)
+}
+
+/// Like [`load_workflow`], but describe a file that can't be loaded by the file at fault and the
+/// position of its YAML error, so discovery can report it and carry on
+pub fn inspect_workflow(fs: &dyn FileSystem, path: &Path, strict: bool) -> Result<Workflow, DiscoveryFailure> {
+    load(fs, path, strict).map_err(LoadError::into_failure)
+}
+
+/// Read and merge a workflow file like [`inspect_workflow`], without checking its arguments'
+/// dependencies or unknown fields, for `wf lint` to report those itself
+pub fn parse_workflow(fs: &dyn FileSystem, path: &Path) -> Result<Workflow, DiscoveryFailure> {
+    parse(fs, path, false).map_err(LoadError::into_failure)
+}
+
+/// The YAML of a workflow file with its `extends` base and `include_arguments` merged in
+pub fn compose_workflow(fs: &dyn FileSystem, path: &Path) -> Result<Value, DiscoveryFailure> {
+    compose(fs, path, &mut Vec::new()).map_err(LoadError::into_failure)
+}
+
+/// Why a workflow file could not be loaded
+struct LoadError {
+    /// The file at fault: the workflow itself, its base or a fragment
+    path:     PathBuf,
+    location: Option<Location>,
+    error:    WorkflowError
+}
+
+impl LoadError {
+    fn new(path: &Path, error: impl Into<WorkflowError>) -> Self {
+        Self { path: path.to_path_buf(), location: None, error: error.into() }
+    }
+
+    fn yaml(path: &Path, e: serde_yaml::Error) -> Self {
+        Self {
+            path:     path.to_path_buf(),
+            location: e.location(),
+            error:    StorageError::Serialization(e.to_string()).into()
+        }
+    }
+
+    fn into_failure(self) -> DiscoveryFailure {
+        let message = self.error.to_string();
+        // The position is kept apart, so drop it from the message
+        let message = match &self.location {
+            Some(location) => {
+                message.replacen(&format!(" at line {} column {}", location.line(), location.column()), "", 1)
+            }
+            None => message
+        };
+
+        DiscoveryFailure {
+            path: self.path.display().to_string(),
+            line: self.location.as_ref().map(Location::line),
+            column: self.location.as_ref().map(Location::column),
+            message
+        }
+    }
+}
+
+fn load(fs: &dyn FileSystem, path: &Path, strict: bool) -> Result<Workflow, LoadError> {
+    let invalid = |reason: String| {
+        LoadError::new(
+            path,
+            ValidationError::Other(t_params!("error_invalid_workflow_file", &[&path.display().to_string(), &reason]))
+        )
+    };
+
+    let workflow = parse(fs, path, strict)?;
+    resolution_order(&workflow.arguments, &TeraInspector).map_err(|e| invalid(e.to_string()))?;
+    // A broken `validation.pattern` would otherwise only show once someone types a value
+    for validation in workflow.arguments.iter().filter_map(|arg| arg.validation.as_ref()) {
+        validation.regex().map_err(invalid)?;
+    }
+    Ok(workflow)
+}
+
+fn parse(fs: &dyn FileSystem, path: &Path, strict: bool) -> Result<Workflow, LoadError> {
+    let mut composed = compose(fs, path, &mut Vec::new())?;
+    if strict {
+        let unknown = unknown_fields(&workflow_schema(), &composed);
+        if !unknown.is_empty() {
+            return Err(LoadError::new(path, ValidationError::UnknownFields(unknown.join(", "))));
+        }
+    }
+    // Like other fields the format doesn't know, a file can't set where it was discovered
+    if let Value::Mapping(fields) = &mut composed {
+        for field in DISCOVERY_FIELDS {
+            fields.remove(field);
+        }
+    }
+    serde_yaml::from_value(composed).map_err(|e| locate(fs, path, e))
+}
+
+/// Errors from the merged YAML carry no position. When the same error comes up parsing the file
+/// on its own, it lies in the file itself and that parse knows where (and which field).
+fn locate(fs: &dyn FileSystem, path: &Path, e: serde_yaml::Error) -> LoadError {
+    let message = e.to_string();
+    let located = fs
+        .read_to_string(path)
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<Workflow>(&content).err())
+        .filter(|located| located.location().is_some() && located.to_string().contains(&message));
+
+    LoadError::yaml(path, located.unwrap_or(e))
+}
+
+/// Read a workflow file as YAML, on top of its `extends` base and `include_arguments`.
+/// `chain` holds the files extending the current one, to catch cycles.
+fn compose(fs: &dyn FileSystem, path: &Path, chain: &mut Vec<PathBuf>) -> Result<Value, LoadError> {
+    if !fs.exists(path) {
+        return Err(LoadError::new(
+            path,
+            StorageError::Io(t_params!("error_workflow_file_not_found", &[&path.display().to_string()]))
+        ));
+    }
+    if chain.iter().any(|extending| extending == path) {
+        let cycle: Vec<String> = chain.iter().chain([&path.to_path_buf()]).map(|p| p.display().to_string()).collect();
+        return Err(LoadError::new(
+            path,
+            ValidationError::Other(t_params!("error_extends_cycle", &[&cycle.join(" -> ")]))
+        ));
+    }
+
+    let content = fs.read_to_string(path).map_err(|e| LoadError::new(path, e))?;
+    let mut own: Value = serde_yaml::from_str(&content).map_err(|e| LoadError::yaml(path, e))?;
+    let Value::Mapping(fields) = &mut own else {
+        // Not a mapping: let parsing the workflow report it
+        return Ok(own);
+    };
+    let extends = fields.remove("extends");
+    let includes = fields.remove("include_arguments");
+    // Marks this file only, not the workflows extending it
+    fields.remove("abstract");
+    let dir = path.parent().unwrap_or(Path::new(""));
+
+    let mut composed = match extends {
+        Some(base) => {
+            let base: String = serde_yaml::from_value(base).map_err(|e| LoadError::yaml(path, e))?;
+            chain.push(path.to_path_buf());
+            let composed = compose(fs, &dir.join(base), chain)?;
+            chain.pop();
+            composed
+        }
+        None => Value::Mapping(Mapping::new())
+    };
+
+    if let Some(includes) = includes {
+        let references: Vec<String> = serde_yaml::from_value(includes).map_err(|e| LoadError::yaml(path, e))?;
+        let fragments = references
+            .iter()
+            .map(|reference| load_fragment(fs, path, dir, reference))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut included = Mapping::new();
+        included.insert("arguments".into(), Value::Sequence(fragments));
+        composed = merge(composed, Value::Mapping(included));
+    }
+
+    Ok(merge(composed, own))
+}
+
+/// Load a shared argument, e.g. `k8s/namespace` from `_arguments/k8s/namespace.yaml`, from the
+/// closest fragments directory that has it. Without a `name` the argument is named after the file.
+/// A missing fragment is blamed on `workflow`, the file including it.
+fn load_fragment(fs: &dyn FileSystem, workflow: &Path, dir: &Path, reference: &str) -> Result<Value, LoadError> {
+    let candidates = dir.ancestors().flat_map(|ancestor| {
+        ["yaml", "yml"]
+            .map(|extension| ancestor.join(ARGUMENT_FRAGMENTS_DIR).join(format!("{}.{}", reference, extension)))
+    });
+
+    for candidate in candidates {
+        if !fs.exists(&candidate) {
+            continue;
+        }
+
+        let content = fs.read_to_string(&candidate).map_err(|e| LoadError::new(&candidate, e))?;
+        let mut fragment: Value = serde_yaml::from_str(&content).map_err(|e| LoadError::yaml(&candidate, e))?;
+        if let Value::Mapping(fields) = &mut fragment
+            && !fields.contains_key("name")
+            && let Some(stem) = candidate.file_stem()
+        {
+            fields.insert("name".into(), stem.to_string_lossy().into_owned().into());
+        }
+        return Ok(fragment);
+    }
+
+    Err(LoadError::new(
+        workflow,
+        StorageError::Io(t_params!("error_argument_fragment_not_found", &[reference, ARGUMENT_FRAGMENTS_DIR]))
+    ))
+}
+
+/// Merge `over` onto `base`: mappings field by field, `arguments` by name (so an argument can
+/// override single fields of an inherited one), anything else replaced.
+fn merge(base: Value, over: Value) -> Value {
+    match (base, over) {
+        (Value::Mapping(mut base), Value::Mapping(over)) => {
+            for (key, value) in over {
+                let merged = match (key.as_str(), base.remove(&key), value) {
+                    (Some("arguments"), Some(Value::Sequence(inherited)), Value::Sequence(own)) => {
+                        Value::Sequence(merge_arguments(inherited, own))
+                    }
+                    (_, Some(existing), value) => merge(existing, value),
+                    (_, None, value) => value
+                };
+                base.insert(key, merged);
+            }
+            Value::Mapping(base)
+        }
+        (_, over) => over
+    }
+}
+
+/// Merge argument lists by `name`: matching arguments are merged in place, new ones appended
+fn merge_arguments(mut inherited: Vec<Value>, own: Vec<Value>) -> Vec<Value> {
+    for argument in own {
+        let position = argument
+            .get("name")
+            .and_then(|name| inherited.iter().position(|existing| existing.get("name") == Some(name)));
+        match position {
+            Some(index) => inherited[index] = merge(std::mem::take(&mut inherited[index]), argument),
+            None => inherited.push(argument)
+        }
+    }
+    inherited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::filesystem::mock::MockFileSystem;
+
+    #[test]
+    fn recognizes_workflow_extensions() {
+        assert!(is_workflow_file(Path::new("/w/deploy.yaml")));
+        assert!(is_workflow_file(Path::new("/w/deploy.yml")));
+        assert!(!is_workflow_file(Path::new("/w/readme.md")));
+        assert!(!is_workflow_file(Path::new("/w/yaml")));
+        assert!(is_workflow_file(Path::new("/w/_base.yaml")));
+    }
+
+    #[test]
+    fn only_explicit_bases_are_left_out() {
+        let fs = MockFileSystem::new();
+        fs.write(Path::new("/w/deploy.yaml"), "name: deploy\ncommand: deploy").unwrap();
+        fs.write(Path::new("/w/deploy-prod.yaml"), "extends: deploy.yaml\nname: deploy-prod").unwrap();
+        fs.write(Path::new("/w/_k8s.yaml"), "extends: ./common.yaml").unwrap();
+        fs.write(Path::new("/w/common.yaml"), "abstract: true\ndescription: common").unwrap();
+        fs.write(Path::new("/w/k8s/logs.yaml"), "extends: ../_k8s.yaml\nname: logs").unwrap();
+        fs.write(Path::new("/w/_cleanup.yaml"), "name: cleanup").unwrap();
+        fs.write(Path::new("/w/_arguments/k8s/namespace.yaml"), "description: ns").unwrap();
+
+        let files = find_workflow_files(&fs, Path::new("/w")).unwrap();
+        let expected = ["/w/_cleanup.yaml", "/w/deploy-prod.yaml", "/w/deploy.yaml", "/w/k8s/logs.yaml"];
+        assert_eq!(files, expected.map(PathBuf::from));
+    }
+
+    #[test]
+    fn abstract_is_not_inherited() {
+        let fs = MockFileSystem::new();
+        fs.write(Path::new("/w/common.yaml"), "abstract: true\ndescription: common").unwrap();
+        fs.write(Path::new("/w/logs.yaml"), "extends: common.yaml\nname: logs").unwrap();
+
+        let composed = compose_workflow(&fs, Path::new("/w/logs.yaml")).unwrap();
+        assert!(composed.get("abstract").is_none());
+        assert_eq!(composed.get("description").and_then(Value::as_str), Some("common"));
+    }
+
+    fn k8s_repo() -> MockFileSystem {
+        let fs = MockFileSystem::new();
+        fs.write(
+            Path::new("/w/_k8s.yaml"),
+            r#"
+description: Kubernetes workflow
+tags: [k8s]
+shells: []
+include_arguments: [k8s/context]
+arguments:
+  - name: namespace
+    arg_type: Enum
+    description: Namespace
+    enum_command: kubectl get ns -o name
+"#
+        )
+        .unwrap();
+        fs.write(
+            Path::new("/w/_arguments/k8s/context.yaml"),
+            "arg_type: Enum
+description: Kube context
+enum_command: kubectl config get-contexts -o name"
+        )
+        .unwrap();
+        fs.write(
+            Path::new("/w/_arguments/cluster.yaml"),
+            "name: cluster
+description: Cluster"
+        )
+        .unwrap();
+        fs
+    }
+
+    #[test]
+    fn extends_merges_base_and_overrides_argument_fields() {
+        let fs = k8s_repo();
+        let path = Path::new("/w/logs.yaml");
+        fs.write(
+            path,
+            r#"
+extends: _k8s.yaml
+name: logs
+command: kubectl logs -n {{ namespace }} --context {{ context }}
+arguments:
+  - name: namespace
+    default_value: default
+  - name: since
+    description: Since
+"#
+        )
+        .unwrap();
+
+        let workflow = load_workflow(&fs, path, false).unwrap();
+        assert_eq!(workflow.description, "Kubernetes workflow");
+        assert_eq!(workflow.tags, vec!["k8s".to_string()]);
+        let names: Vec<&str> = workflow.arguments.iter().map(|arg| arg.name.as_str()).collect();
+        assert_eq!(names, vec!["context", "namespace", "since"]);
+
+        let namespace = &workflow.arguments[1];
+        assert_eq!(namespace.default_value.as_deref(), Some("default"));
+        assert_eq!(namespace.enum_command.as_deref(), Some("kubectl get ns -o name"));
+    }
+
+    #[test]
+    fn included_arguments_are_found_above_the_workflow() {
+        let fs = k8s_repo();
+        let path = Path::new("/w/ops/drain.yaml");
+        fs.write(
+            path,
+            r#"
+name: drain
+description: Drain a node
+command: kubectl drain {{ node }}
+tags: []
+shells: []
+include_arguments: [cluster, k8s/context]
+arguments:
+  - name: cluster
+    description: Cluster to drain
+"#
+        )
+        .unwrap();
+
+        let workflow = load_workflow(&fs, path, false).unwrap();
+        let names: Vec<&str> = workflow.arguments.iter().map(|arg| arg.name.as_str()).collect();
+        assert_eq!(names, vec!["cluster", "context"]);
+        assert_eq!(workflow.arguments[0].description, "Cluster to drain");
+    }
+
+    #[test]
+    fn missing_fragment_is_an_error() {
+        let fs = k8s_repo();
+        let path = Path::new("/w/x.yaml");
+        fs.write(
+            path,
+            "name: x
+description: x
+command: x
+tags: []
+shells: []
+include_arguments: [nope]"
+        )
+        .unwrap();
+        assert!(matches!(load_workflow(&fs, path, false), Err(WorkflowError::Storage(StorageError::Io(_)))));
+    }
+
+    #[test]
+    fn extends_cycle_is_an_error() {
+        let fs = MockFileSystem::new();
+        fs.write(Path::new("/w/_a.yaml"), "extends: _b.yaml").unwrap();
+        fs.write(Path::new("/w/_b.yaml"), "extends: _a.yaml").unwrap();
+        let path = Path::new("/w/x.yaml");
+        fs.write(
+            path,
+            "extends: _a.yaml
+name: x"
+        )
+        .unwrap();
+
+        assert!(matches!(load_workflow(&fs, path, false), Err(WorkflowError::Validation(ValidationError::Other(_)))));
+    }
+
+    #[test]
+    fn loads_workflow_from_file() {
+        let fs = MockFileSystem::new();
+        let path = Path::new("/repo/ops/deploy.yaml");
+        fs.write(path, "name: deploy\ndescription: Deploy\ncommand: echo deploy\narguments: []\ntags: []\nshells: []")
+            .unwrap();
+
+        let workflow = load_workflow(&fs, path, false).unwrap();
+        assert_eq!(workflow.name, "deploy");
+        assert_eq!(workflow.command, "echo deploy");
+    }
+
+    #[test]
+    fn files_cannot_set_where_they_were_discovered() {
+        let fs = MockFileSystem::new();
+        let path = Path::new("/repo/deploy.yaml");
+        fs.write(
+            path,
+            "name: deploy\ndescription: Deploy\ncommand: echo deploy\narguments: []\ntags: []\nshells: []\nnamespace: \
+             ../../etc\norigin: { dir: /etc, label: global }"
+        )
+        .unwrap();
+
+        let workflow = load_workflow(&fs, path, false).unwrap();
+        assert!(workflow.namespace.is_none() && workflow.origin.is_none());
+        assert!(matches!(
+            load_workflow(&fs, path, true),
+            Err(WorkflowError::Validation(ValidationError::UnknownFields(fields))) if fields.contains("namespace")
+        ));
+    }
+
+    #[test]
+    fn missing_file_returns_error() {
+        let fs = MockFileSystem::new();
+        assert!(load_workflow(&fs, Path::new("/repo/missing.yaml"), false).is_err());
+    }
+
+    #[test]
+    fn dependency_cycle_is_reported_on_load() {
+        let fs = MockFileSystem::new();
+        let path = Path::new("/repo/cycle.yaml");
+        fs.write(
+            path,
+            r#"
+name: cycle
+description: Cycle
+command: echo {{ a }} {{ b }}
+tags: []
+shells: []
+arguments:
+  - { name: a, description: "", depends_on: [b] }
+  - { name: b, description: "", depends_on: [a] }
+"#
+        )
+        .unwrap();
+
+        assert!(matches!(load_workflow(&fs, path, false), Err(WorkflowError::Validation(ValidationError::Other(_)))));
+    }
+
+    #[test]
+    fn invalid_yaml_returns_error() {
+        let fs = MockFileSystem::new();
+        let path = Path::new("/repo/bad.yaml");
+        fs.write(path, "not: [valid: workflow").unwrap();
+
+        assert!(load_workflow(&fs, path, false).is_err());
+    }
+
+    #[test]
+    fn strict_loading_rejects_unknown_fields() {
+        let fs = MockFileSystem::new();
+        let path = Path::new("/repo/typo.yaml");
+        fs.write(
+            path,
+            r#"
+name: typo
+description: Typo
+command: echo {{ a }}
+tags: []
+shells: []
+arguments:
+  - { name: a, description: "", default_vaule: x }
+"#
+        )
+        .unwrap();
+
+        assert!(load_workflow(&fs, path, false).is_ok());
+        let failure = inspect_workflow(&fs, path, true).unwrap_err();
+        assert_eq!(failure.path, "/repo/typo.yaml");
+        assert_eq!(failure.message, t_params!("error_unknown_workflow_fields", &["arguments[0].default_vaule"]));
+    }
+}
@@ -4,8 +4,14 @@ pub mod event;
 pub mod executor;
 pub mod filesystem;
 pub mod git;
+pub mod ignore;
 pub mod journal;
+pub mod lint;
+pub mod loader;
 pub mod output;
 pub mod prompt;
 pub mod resolver;
+pub mod schema;
+pub mod secret;
+pub mod sink;
 pub mod storage;
@@ -1,7 +1,17 @@
 use crate::port::output::{OutputWriter, Spinner};
 
+/// cliclack-backed output. Everything except `raw` already goes to stderr;
+/// `stderr_only` moves `raw` there too so stdout carries nothing but the result.
 #[derive(Default)]
-pub struct CliOutput;
+pub struct CliOutput {
+    stderr_only: bool
+}
+
+impl CliOutput {
+    pub fn stderr_only() -> Self {
+        Self { stderr_only: true }
+    }
+}
 
 impl OutputWriter for CliOutput {
     fn info(&self, msg: &str) {
@@ -29,7 +39,11 @@ impl OutputWriter for CliOutput {
     }
 
     fn raw(&self, msg: &str) {
-        println!("{}", msg);
+        if self.stderr_only {
+            eprintln!("{}", msg);
+        } else {
+            println!("{}", msg);
+        }
     }
 
     fn spinner(&self) -> Box<dyn Spinner> {
@@ -48,3 +62,68 @@ impl Spinner for CliSpinner {
         self.0.stop(msg);
     }
 }
+
+#[cfg(test)]
+pub mod mock {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Output writer that records messages instead of printing them
+    #[derive(Default)]
+    pub struct MockOutput {
+        messages: Mutex<Vec<String>>
+    }
+
+    impl MockOutput {
+        pub fn messages(&self) -> Vec<String> {
+            self.messages.lock().unwrap().clone()
+        }
+
+        fn record(&self, msg: &str) {
+            self.messages.lock().unwrap().push(msg.to_string());
+        }
+    }
+
+    impl OutputWriter for MockOutput {
+        fn info(&self, msg: &str) {
+            self.record(msg);
+        }
+
+        fn success(&self, msg: &str) {
+            self.record(msg);
+        }
+
+        fn warning(&self, msg: &str) {
+            self.record(msg);
+        }
+
+        fn step(&self, msg: &str) {
+            self.record(msg);
+        }
+
+        fn intro(&self, title: &str) {
+            self.record(title);
+        }
+
+        fn outro(&self, msg: &str) {
+            self.record(msg);
+        }
+
+        fn raw(&self, msg: &str) {
+            self.record(msg);
+        }
+
+        fn spinner(&self) -> Box<dyn Spinner> {
+            Box::new(MockSpinner)
+        }
+    }
+
+    struct MockSpinner;
+
+    impl Spinner for MockSpinner {
+        fn start(&self, _msg: &str) {}
+
+        fn stop(&self, _msg: &str) {}
+    }
+}
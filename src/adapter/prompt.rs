@@ -1,6 +1,6 @@
 use crate::{
     domain::error::{PromptError, WorkflowError},
-    port::prompt::{SelectOption, UserPrompt}
+    port::prompt::{SelectOption, TextValidator, UserPrompt}
 };
 
 fn handle_interact_result<T>(result: Result<T, std::io::Error>) -> Result<T, WorkflowError> {
@@ -75,6 +75,26 @@ impl UserPrompt for CliPrompt {
         handle_interact_result(input.interact())
     }
 
+    fn text_validated(
+        &self,
+        prompt: &str,
+        default: Option<&str>,
+        validate: TextValidator
+    ) -> Result<String, WorkflowError> {
+        let mut input: cliclack::Input =
+            cliclack::input(prompt).validate(move |value: &String| validate(value.as_str()));
+        if let Some(d) = default {
+            input = input.default_input(d);
+        }
+        handle_interact_result(input.interact())
+    }
+
+    fn password(&self, prompt: &str, validate: TextValidator) -> Result<String, WorkflowError> {
+        let mut password =
+            cliclack::password(prompt).mask('•').validate(move |value: &String| validate(value.as_str()));
+        handle_interact_result(password.interact())
+    }
+
     fn confirm(&self, prompt: &str, default: bool) -> Result<bool, WorkflowError> {
         let mut confirm = cliclack::confirm(prompt).initial_value(default);
         handle_interact_result(confirm.interact())
@@ -98,12 +118,18 @@ pub mod mock {
 
     /// Mock implementation that returns scripted responses in order
     pub struct MockPrompt {
-        responses: Mutex<Vec<MockPromptResponse>>
+        responses: Mutex<Vec<MockPromptResponse>>,
+        rejected:  Mutex<Vec<String>>
     }
 
     impl MockPrompt {
         pub fn new(responses: Vec<MockPromptResponse>) -> Self {
-            Self { responses: Mutex::new(responses) }
+            Self { responses: Mutex::new(responses), rejected: Mutex::new(vec![]) }
+        }
+
+        /// Messages of the validated text inputs that were rejected and asked again
+        pub fn rejected(&self) -> Vec<String> {
+            self.rejected.lock().unwrap().clone()
         }
     }
 
@@ -147,6 +173,27 @@ pub mod mock {
             }
         }
 
+        /// Consumes Text responses until one passes `validate`, like a user retyping
+        fn text_validated(
+            &self,
+            prompt: &str,
+            default: Option<&str>,
+            validate: TextValidator
+        ) -> Result<String, WorkflowError> {
+            loop {
+                let value = self.text(prompt, default)?;
+                match validate(&value) {
+                    Ok(()) => return Ok(value),
+                    Err(message) => self.rejected.lock().unwrap().push(message)
+                }
+            }
+        }
+
+        /// Same as `text_validated`: takes Text responses
+        fn password(&self, prompt: &str, validate: TextValidator) -> Result<String, WorkflowError> {
+            self.text_validated(prompt, None, validate)
+        }
+
         fn confirm(&self, _prompt: &str, _default: bool) -> Result<bool, WorkflowError> {
             let mut responses = self.responses.lock().unwrap();
             match responses.remove(0) {
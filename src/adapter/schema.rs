@@ -0,0 +1,167 @@
+//! JSON Schema of the workflow file format
+//!
+//! Generated from the serde structs, so descriptions come from their doc comments. On top of the
+//! `Workflow` fields, files may use `extends`, `include_arguments` and `abstract`; a file that
+//! extends another one can leave the required fields to its base, and an abstract base can leave
+//! them to the files extending it. The same schema tells strict loading which
+//! fields are unknown.
+
+use serde_json::{Map, Value, json};
+
+use crate::domain::workflow::Workflow;
+
+/// Schema for workflow YAML files, as served by `wf schema`
+pub fn workflow_schema() -> Value {
+    let mut schema = serde_json::to_value(schemars::schema_for!(Workflow)).unwrap_or_default();
+
+    if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+        properties.insert(
+            "extends".to_string(),
+            json!({
+                "description": "Workflow file this one builds on, relative to it; its fields are merged in and \
+                                arguments are merged by name",
+                "type": "string"
+            })
+        );
+        properties.insert(
+            "abstract".to_string(),
+            json!({
+                "description": "Marks a base meant to be extended: discovery doesn't list it as a workflow",
+                "type": "boolean"
+            })
+        );
+        properties.insert(
+            "include_arguments".to_string(),
+            json!({
+                "description": "Shared arguments to add, e.g. `k8s/namespace` for \
+                                `_arguments/k8s/namespace.yaml` next to this file or in a directory above it",
+                "type": "array",
+                "items": { "type": "string" }
+            })
+        );
+    }
+
+    if let Some(object) = schema.as_object_mut()
+        && let Some(required) = object.remove("required")
+    {
+        object.insert(
+            "anyOf".to_string(),
+            json!([{ "required": ["extends"] }, { "required": ["abstract"] }, { "required": required }])
+        );
+    }
+
+    schema
+}
+
+/// Fields of a workflow file (after `extends` and `include_arguments` are merged in) that
+/// `schema` doesn't know, as paths like `arguments[0].default_vaule`
+pub fn unknown_fields(schema: &Value, value: &serde_yaml::Value) -> Vec<String> {
+    let mut found = Vec::new();
+    collect_unknown_fields(schema, schema, value, "", &mut found);
+    found
+}
+
+fn collect_unknown_fields(
+    root: &Value,
+    schema: &Value,
+    value: &serde_yaml::Value,
+    path: &str,
+    found: &mut Vec<String>
+) {
+    let Some(schema) = object_schema(root, schema) else {
+        return;
+    };
+
+    match value {
+        serde_yaml::Value::Mapping(fields) => {
+            let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+                return;
+            };
+            for (key, field) in fields {
+                let Some(key) = key.as_str() else {
+                    continue;
+                };
+                let at = if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) };
+                match properties.get(key) {
+                    Some(property) => collect_unknown_fields(root, property, field, &at, found),
+                    None => found.push(at)
+                }
+            }
+        }
+        serde_yaml::Value::Sequence(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    collect_unknown_fields(root, item_schema, item, &format!("{}[{}]", path, index), found);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The schema describing an object or array, following `$ref` into `$defs` and picking it out of
+/// the `anyOf` generated for optional fields
+fn object_schema<'a>(root: &'a Value, schema: &'a Value) -> Option<&'a Map<String, Value>> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let target = root.pointer(reference.strip_prefix('#')?)?;
+        return object_schema(root, target);
+    }
+    let object = schema.as_object()?;
+    if object.contains_key("properties") || object.contains_key("items") {
+        return Some(object);
+    }
+    let variants = object.get("anyOf").or_else(|| object.get("oneOf")).and_then(Value::as_array)?;
+    variants.iter().find_map(|variant| object_schema(root, variant))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMITTED_SCHEMA: &str = include_str!("../../config/schema/workflow.schema.json");
+
+    #[test]
+    fn committed_schema_is_up_to_date() {
+        let committed: Value = serde_json::from_str(COMMITTED_SCHEMA).unwrap();
+        assert!(
+            committed == workflow_schema(),
+            "config/schema/workflow.schema.json is out of date, regenerate it with `wf schema > \
+             config/schema/workflow.schema.json`"
+        );
+    }
+
+    #[test]
+    fn schema_describes_fields_from_doc_comments() {
+        let schema = workflow_schema();
+        let description = schema.pointer("/properties/execute/description").and_then(Value::as_str);
+        assert_eq!(description, Some("Run the rendered command instead of copying it (same as `--exec`)"));
+        assert!(schema.pointer("/properties/extends").is_some());
+        assert!(schema.pointer("/$defs/WorkflowArgument/properties/default_value").is_some());
+    }
+
+    #[test]
+    fn typos_are_unknown_fields() {
+        let workflow: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+name: x
+description: x
+command: echo
+tags: []
+shells: []
+exeucte: true
+arguments:
+  - name: a
+    description: ""
+    default_vaule: b
+    validation: { patern: "x" }
+  - { name: b, description: "", path: { must_exist: true } }
+"#
+        )
+        .unwrap();
+
+        assert_eq!(
+            unknown_fields(&workflow_schema(), &workflow),
+            vec!["exeucte", "arguments[0].default_vaule", "arguments[0].validation.patern"]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::error::WorkflowError,
+    port::{output::OutputWriter, prompt::UserPrompt, sink::ResultSink},
+    t, t_params
+};
+
+/// Copies the command to the system clipboard, asking first when interactive and `confirm` is set
+pub struct ClipboardSink {
+    prompt:  Arc<dyn UserPrompt>,
+    output:  Arc<dyn OutputWriter>,
+    confirm: bool
+}
+
+impl ClipboardSink {
+    pub fn new(prompt: Arc<dyn UserPrompt>, output: Arc<dyn OutputWriter>, confirm: bool) -> Self {
+        Self { prompt, output, confirm }
+    }
+}
+
+impl ResultSink for ClipboardSink {
+    fn deliver(&self, command: &str, interactive: bool) -> Result<(), WorkflowError> {
+        let should_copy =
+            !interactive || !self.confirm || self.prompt.confirm(&t!("prompt_copy_to_clipboard"), true).unwrap_or(true);
+
+        if !should_copy {
+            self.output.info(&t!("cli_clipboard_copy_skipped"));
+            return Ok(());
+        }
+
+        match copy_to_clipboard(command) {
+            Ok(()) => {
+                self.output.success(&t!("cli_command_copied_to_clipboard"));
+            }
+            Err(e) => {
+                self.output.warning(&t_params!("cli_failed_to_copy_to_clipboard", &[&e.to_string()]));
+                self.output.info(&t!("cli_command_can_now_be_pasted_and_executed_in_terminal"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes only the command to stdout so it can be captured with `$(wf --print)`
+pub struct StdoutSink;
+
+impl ResultSink for StdoutSink {
+    fn deliver(&self, command: &str, _interactive: bool) -> Result<(), WorkflowError> {
+        println!("{}", command);
+        Ok(())
+    }
+}
+
+/// Helper function to copy text to clipboard
+fn copy_to_clipboard(text: &str) -> Result<(), WorkflowError> {
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|e| WorkflowError::Other(t_params!("error_failed_to_create_clipboard_context", &[&e.to_string()])))?;
+    clipboard
+        .set_text(text.to_owned())
+        .map_err(|e| WorkflowError::Other(t_params!("error_failed_to_set_clipboard_contents", &[&e.to_string()])))?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod mock {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Sink that records every delivered command
+    #[derive(Default)]
+    pub struct MockSink {
+        pub delivered: Mutex<Vec<String>>
+    }
+
+    impl ResultSink for MockSink {
+        fn deliver(&self, command: &str, _interactive: bool) -> Result<(), WorkflowError> {
+            self.delivered.lock().unwrap().push(command.to_string());
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::{
+        output::mock::MockOutput,
+        prompt::mock::{MockPrompt, MockPromptResponse}
+    };
+
+    #[test]
+    fn clipboard_sink_skips_copy_when_declined() {
+        let prompt = Arc::new(MockPrompt::new(vec![MockPromptResponse::Confirm(false)]));
+        let output = Arc::new(MockOutput::default());
+        let sink = ClipboardSink::new(prompt, output.clone(), true);
+
+        sink.deliver("echo hi", true).unwrap();
+        assert_eq!(output.messages(), vec![t!("cli_clipboard_copy_skipped")]);
+    }
+}
@@ -182,8 +182,12 @@ mod tests {
             source_url:  None,
             author:      None,
             author_url:  None,
+            execute:     false,
+            steps:       vec![],
             shells:      vec![],
-            tags:        vec![]
+            tags:        vec![],
+            namespace:   None,
+            origin:      None
         }
     }
 
@@ -286,7 +290,8 @@ mod tests {
         let resolve_event = WorkflowEvent::WorkflowArgumentsResolved(WorkflowArgumentsResolvedEvent {
             event_id:  Uuid::new_v4().to_string(),
             timestamp: Utc::now(),
-            arguments: std::collections::HashMap::new()
+            arguments: std::collections::HashMap::new(),
+            context:   None
         });
         store.store_events(session_id, &[resolve_event]).await.unwrap();
 
@@ -5,7 +5,13 @@ use std::{collections::HashMap, fmt::Debug};
 use clap::{Parser, Subcommand};
 use serde::{Deserialize, Serialize};
 
-use crate::domain::workflow::Workflow;
+use crate::{
+    domain::{
+        shell::Shell,
+        workflow::{DiscoveryFailure, StepRecord, Workflow}
+    },
+    t_params
+};
 
 /// Main CLI application
 #[derive(Parser, Debug)]
@@ -13,23 +19,51 @@ use crate::domain::workflow::Workflow;
 pub struct WorkflowCli {
     /// Subcommands
     #[command(subcommand)]
-    pub command: Option<WorkflowCliCommand>
+    pub command:             Option<WorkflowCliCommand>,
+    /// Provide an argument value up front instead of being prompted (repeatable)
+    #[arg(long = "arg", value_name = "NAME=VALUE", value_parser = parse_argument_assignment, global = true)]
+    pub args:                Vec<(String, String)>,
+    /// Fail on missing arguments instead of prompting (for scripts and CI)
+    #[arg(long, global = true)]
+    pub no_prompt:           bool,
+    /// Print only the rendered command to stdout instead of copying it (prompts go to stderr)
+    #[arg(long, visible_alias = "output-only", global = true)]
+    pub print:               bool,
+    /// Run the rendered command instead of copying it, and record how it went
+    #[arg(long, global = true)]
+    pub exec:                bool,
+    /// Reject workflow files with fields the format doesn't know (e.g. typos like `default_vaule`)
+    #[arg(long, global = true)]
+    pub deny_unknown_fields: bool,
+    /// Profile from config.toml to run with: its workflows, history, sync remote and argument
+    /// defaults
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile:             Option<String>,
+    /// Only offer workflows that list this shell (or no shell at all) in the chooser
+    #[arg(long, value_enum)]
+    pub shell:               Option<Shell>
+}
+
+/// Parse a `--arg NAME=VALUE` assignment. Only the first `=` splits, so values may contain `=`.
+pub fn parse_argument_assignment(raw: &str) -> Result<(String, String), String> {
+    match raw.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_string(), value.to_string())),
+        _ => Err(t_params!("error_invalid_argument_assignment", &[raw]))
+    }
 }
 
 /// Available CLI commands
 #[derive(Subcommand, Debug)]
 pub enum WorkflowCliCommand {
-    /// Sync workflows from remote git repository
+    /// Sync workflows from the configured remotes (see `wf remote`)
     Sync {
-        /// SSH key path for authentication
-        #[arg(long)]
-        ssh_key:    Option<String>,
-        /// Remote repository URL (optional, uses configured remote)
-        #[arg(long)]
-        remote_url: Option<String>,
-        /// Branch to sync (defaults to main)
-        #[arg(long, default_value = "main")]
-        branch:     String
+        /// Remote to sync; all of them when left out
+        name: Option<String>
+    },
+    /// Manage the Git remotes that `wf sync` pulls workflows from
+    Remote {
+        #[command(subcommand)]
+        command: RemoteCommands
     },
     /// Language management commands
     Lang {
@@ -41,12 +75,44 @@ pub enum WorkflowCliCommand {
         #[command(subcommand)]
         command: StorageCommands
     },
+    /// Read and change settings in config.toml
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands
+    },
     /// List available workflows
     List,
-    /// Select a workflow
+    /// Run a discovered workflow by name, slug or fuzzy match
+    Run {
+        /// Workflow name (exact, slug or a unique fuzzy match)
+        name: String
+    },
+    /// Run a workflow straight from a YAML file
     File {
         /// Path to the workflow file
         file: String
+    },
+    /// Check workflow files for mistakes without running them
+    Lint {
+        /// Workflow files or directories (defaults to every directory on the search path)
+        paths:  Vec<String>,
+        /// Print the findings as JSON
+        #[arg(long)]
+        json:   bool,
+        /// Fail on warnings too
+        #[arg(long)]
+        strict: bool
+    },
+    /// Print the JSON Schema of workflow files, for editor completion and validation
+    Schema,
+    /// Print a key binding widget that inserts the resolved command into the prompt line
+    ShellInit {
+        /// Shell to generate the widget for
+        #[arg(value_enum)]
+        shell: Shell,
+        /// Key binding in the shell's own notation (defaults to Ctrl-G)
+        #[arg(long)]
+        key:   Option<String>
     }
 }
 
@@ -64,6 +130,58 @@ pub enum LangCommands {
     List
 }
 
+/// Remote management subcommands
+#[derive(Subcommand, Debug)]
+pub enum RemoteCommands {
+    /// Add a remote; its workflows are synced into a folder (and namespace) named after it
+    Add {
+        /// Name of the remote, e.g. `team`
+        name:    String,
+        /// Repository URL
+        url:     String,
+        /// Branch to sync
+        #[arg(long, default_value = "main")]
+        branch:  String,
+        /// SSH key path for authentication
+        #[arg(long)]
+        ssh_key: Option<String>,
+        /// Only take workflows from this folder of the repository
+        #[arg(long)]
+        path:    Option<String>
+    },
+    /// List the configured remotes
+    List,
+    /// Remove a remote and the workflows synced from it
+    Remove {
+        /// Name of the remote
+        name: String
+    }
+}
+
+/// Settings subcommands
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// Print the value of a setting
+    Get {
+        /// Setting key, e.g. `ui.page_size`
+        key: String
+    },
+    /// Change a setting in config.toml
+    Set {
+        /// Setting key, e.g. `ui.page_size`
+        key:   String,
+        /// New value
+        value: String
+    },
+    /// Put a setting back to its default
+    Unset {
+        /// Setting key, e.g. `clipboard.confirm`
+        key: String
+    },
+    /// List every setting with its value
+    List
+}
+
 /// Storage management subcommands
 #[derive(Subcommand, Debug)]
 pub enum StorageCommands {
@@ -95,21 +213,62 @@ pub struct DiscoverWorkflowsCommand;
 
 #[derive(Debug, Clone)]
 pub struct DiscoverWorkflowsData {
-    pub workflows: Vec<Workflow>
+    pub workflows: Vec<DiscoveredWorkflow>,
+    /// Files that could not be loaded; the other workflows are still discovered
+    pub failures:  Vec<DiscoveryFailure>
+}
+
+/// A workflow found by discovery, with the file it came from
+#[derive(Debug, Clone)]
+pub struct DiscoveredWorkflow {
+    /// Path relative to the workflows directory, e.g. `k8s/logs.yaml`
+    pub file_path: String,
+    pub workflow:  Workflow
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct InteractivelySelectWorkflowCommand;
+pub struct InteractivelySelectWorkflowCommand {
+    /// Restrict the chooser to workflows usable in this shell
+    pub shell: Option<Shell>
+}
 
 pub struct InteractivelySelectWorkflowData {
     pub workflow: Workflow
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SelectWorkflowByNameCommand {
+    pub name:      String,
+    /// Fail on ambiguous names instead of offering a chooser
+    pub no_prompt: bool
+}
+
+pub struct SelectWorkflowByNameData {
+    pub workflow: Workflow
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LoadWorkflowFromFileCommand {
+    pub path: String
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadWorkflowFromFileData {
+    pub workflow: Workflow
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct StartWorkflowCommand;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct ResolveArgumentsCommand;
+pub struct ResolveArgumentsCommand {
+    /// Values passed on the command line, as (name, value) pairs
+    pub arguments: Vec<(String, String)>,
+    /// Error out on missing arguments instead of prompting
+    pub no_prompt: bool,
+    /// The command will be executed, so don't hand it to the result sink
+    pub execute:   bool
+}
 
 #[derive(Debug, Clone)]
 pub struct ResolveArgumentsData {
@@ -117,6 +276,50 @@ pub struct ResolveArgumentsData {
     pub resolved_arguments: HashMap<String, String>
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExecuteWorkflowCommand {
+    /// Run even when the workflow doesn't set `execute: true` (`--exec`)
+    pub forced: bool
+}
+
+#[derive(Debug, Clone)]
+pub struct ExecuteWorkflowData {
+    /// The rendered command, secrets redacted
+    pub command: String,
+    /// How the run went, `None` when the workflow isn't executed
+    pub outcome: Option<ExecutionOutcome>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RunWorkflowStepsCommand {
+    /// Run every step without asking (`--exec`)
+    pub forced:    bool,
+    /// Don't ask per step; hand all steps to the result sink at once
+    pub no_prompt: bool
+}
+
+#[derive(Debug, Clone)]
+pub struct RunWorkflowStepsData {
+    /// Steps handled, in order; stops after the first failed step
+    pub steps: Vec<StepRecord>
+}
+
+/// Result of running a rendered command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionOutcome {
+    /// Exit code, `None` when killed by a signal or never started
+    pub exit_code:   Option<i32>,
+    pub duration_ms: u64,
+    /// Why the process could not be started
+    pub error:       Option<String>
+}
+
+impl ExecutionOutcome {
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ListWorkflowsCommand;
 
@@ -125,14 +328,38 @@ pub struct CompleteWorkflowCommand;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SyncWorkflowsCommand {
-    pub ssh_key:    Option<String>,
-    pub remote_url: Option<String>,
-    pub branch:     String
+    /// Remote to sync; every configured remote when none
+    pub remote:  Option<String>,
+    /// Remotes to sync after this one, one at a time
+    pub pending: Vec<String>,
+    /// Remotes that failed earlier in the same run, reported once the last one is done
+    pub failed:  Vec<String>
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RecordSyncResultCommand {
-    pub commit_id: String
+    pub commit_id: String,
+    /// Remotes to sync once this result is recorded
+    pub pending:   Vec<String>,
+    /// Remotes that failed earlier in the same run
+    pub failed:    Vec<String>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AddRemoteCommand {
+    pub name:    String,
+    pub url:     String,
+    pub branch:  String,
+    pub ssh_key: Option<String>,
+    pub path:    Option<String>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ListRemotesCommand;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RemoveRemoteCommand {
+    pub name: String
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -154,6 +381,25 @@ pub struct SetStorageCommand {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GetCurrentStorageCommand;
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GetConfigCommand {
+    pub key: String
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SetConfigCommand {
+    pub key:   String,
+    pub value: String
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UnsetConfigCommand {
+    pub key: String
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ListConfigCommand;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ListAggregatesCommand;
 
@@ -170,19 +416,46 @@ pub struct DeleteAggregateCommand {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct PurgeStorageCommand;
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LintWorkflowsCommand {
+    /// Files or directories to check, relative to the working directory; empty for every
+    /// directory on the search path
+    pub paths:  Vec<String>,
+    pub json:   bool,
+    /// Warnings fail the lint too
+    pub strict: bool
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PrintSchemaCommand;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ShellInitCommand {
+    pub shell: Shell,
+    /// Key binding override, in the shell's own notation
+    pub key:   Option<String>
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WorkflowCommand {
     // Workflow management
     DiscoverWorkflows(DiscoverWorkflowsCommand),
     ListWorkflows(ListWorkflowsCommand),
     InteractivelySelectWorkflow(InteractivelySelectWorkflowCommand),
+    SelectWorkflowByName(SelectWorkflowByNameCommand),
+    LoadWorkflowFromFile(LoadWorkflowFromFileCommand),
     StartWorkflow(StartWorkflowCommand),
     CompleteWorkflow(CompleteWorkflowCommand),
     ResolveArguments(ResolveArgumentsCommand),
+    ExecuteWorkflow(ExecuteWorkflowCommand),
+    RunWorkflowSteps(RunWorkflowStepsCommand),
 
     // Sync operations
     SyncWorkflows(SyncWorkflowsCommand),
     RecordSyncResult(RecordSyncResultCommand),
+    AddRemote(AddRemoteCommand),
+    ListRemotes(ListRemotesCommand),
+    RemoveRemote(RemoveRemoteCommand),
 
     // Language management
     SetLanguage(SetLanguageCommand),
@@ -195,7 +468,20 @@ pub enum WorkflowCommand {
     ListAggregates(ListAggregatesCommand),
     ReplayAggregate(ReplayAggregateCommand),
     DeleteAggregate(DeleteAggregateCommand),
-    PurgeStorage(PurgeStorageCommand)
+    PurgeStorage(PurgeStorageCommand),
+
+    // Settings
+    GetConfig(GetConfigCommand),
+    SetConfig(SetConfigCommand),
+    UnsetConfig(UnsetConfigCommand),
+    ListConfig(ListConfigCommand),
+
+    // Workflow files
+    LintWorkflows(LintWorkflowsCommand),
+    PrintSchema(PrintSchemaCommand),
+
+    // Shell integration
+    ShellInit(ShellInitCommand)
 }
 
 impl From<SyncWorkflowsCommand> for WorkflowCommand {
@@ -258,6 +544,48 @@ impl From<PurgeStorageCommand> for WorkflowCommand {
     }
 }
 
+impl From<GetConfigCommand> for WorkflowCommand {
+    fn from(val: GetConfigCommand) -> Self {
+        WorkflowCommand::GetConfig(val)
+    }
+}
+
+impl From<SetConfigCommand> for WorkflowCommand {
+    fn from(val: SetConfigCommand) -> Self {
+        WorkflowCommand::SetConfig(val)
+    }
+}
+
+impl From<UnsetConfigCommand> for WorkflowCommand {
+    fn from(val: UnsetConfigCommand) -> Self {
+        WorkflowCommand::UnsetConfig(val)
+    }
+}
+
+impl From<ListConfigCommand> for WorkflowCommand {
+    fn from(val: ListConfigCommand) -> Self {
+        WorkflowCommand::ListConfig(val)
+    }
+}
+
+impl From<LintWorkflowsCommand> for WorkflowCommand {
+    fn from(val: LintWorkflowsCommand) -> Self {
+        WorkflowCommand::LintWorkflows(val)
+    }
+}
+
+impl From<PrintSchemaCommand> for WorkflowCommand {
+    fn from(val: PrintSchemaCommand) -> Self {
+        WorkflowCommand::PrintSchema(val)
+    }
+}
+
+impl From<ShellInitCommand> for WorkflowCommand {
+    fn from(val: ShellInitCommand) -> Self {
+        WorkflowCommand::ShellInit(val)
+    }
+}
+
 impl From<DiscoverWorkflowsCommand> for WorkflowCommand {
     fn from(val: DiscoverWorkflowsCommand) -> Self {
         WorkflowCommand::DiscoverWorkflows(val)
@@ -276,6 +604,18 @@ impl From<InteractivelySelectWorkflowCommand> for WorkflowCommand {
     }
 }
 
+impl From<SelectWorkflowByNameCommand> for WorkflowCommand {
+    fn from(val: SelectWorkflowByNameCommand) -> Self {
+        WorkflowCommand::SelectWorkflowByName(val)
+    }
+}
+
+impl From<LoadWorkflowFromFileCommand> for WorkflowCommand {
+    fn from(val: LoadWorkflowFromFileCommand) -> Self {
+        WorkflowCommand::LoadWorkflowFromFile(val)
+    }
+}
+
 impl From<StartWorkflowCommand> for WorkflowCommand {
     fn from(val: StartWorkflowCommand) -> Self {
         WorkflowCommand::StartWorkflow(val)
@@ -288,14 +628,90 @@ impl From<RecordSyncResultCommand> for WorkflowCommand {
     }
 }
 
+impl From<AddRemoteCommand> for WorkflowCommand {
+    fn from(val: AddRemoteCommand) -> Self {
+        WorkflowCommand::AddRemote(val)
+    }
+}
+
+impl From<ListRemotesCommand> for WorkflowCommand {
+    fn from(val: ListRemotesCommand) -> Self {
+        WorkflowCommand::ListRemotes(val)
+    }
+}
+
+impl From<RemoveRemoteCommand> for WorkflowCommand {
+    fn from(val: RemoveRemoteCommand) -> Self {
+        WorkflowCommand::RemoveRemote(val)
+    }
+}
+
 impl From<ResolveArgumentsCommand> for WorkflowCommand {
     fn from(val: ResolveArgumentsCommand) -> Self {
         WorkflowCommand::ResolveArguments(val)
     }
 }
 
+impl From<ExecuteWorkflowCommand> for WorkflowCommand {
+    fn from(val: ExecuteWorkflowCommand) -> Self {
+        WorkflowCommand::ExecuteWorkflow(val)
+    }
+}
+
+impl From<RunWorkflowStepsCommand> for WorkflowCommand {
+    fn from(val: RunWorkflowStepsCommand) -> Self {
+        WorkflowCommand::RunWorkflowSteps(val)
+    }
+}
+
 impl From<CompleteWorkflowCommand> for WorkflowCommand {
     fn from(val: CompleteWorkflowCommand) -> Self {
         WorkflowCommand::CompleteWorkflow(val)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_argument_assignment() {
+        assert_eq!(parse_argument_assignment("namespace=prod").unwrap(), ("namespace".into(), "prod".into()));
+    }
+
+    #[test]
+    fn argument_assignment_keeps_equals_in_value() {
+        assert_eq!(parse_argument_assignment("filter=a=b").unwrap(), ("filter".into(), "a=b".into()));
+        assert_eq!(parse_argument_assignment("empty=").unwrap(), ("empty".into(), "".into()));
+    }
+
+    #[test]
+    fn argument_assignment_requires_a_name() {
+        assert!(parse_argument_assignment("novalue").is_err());
+        assert!(parse_argument_assignment("=value").is_err());
+    }
+
+    #[test]
+    fn cli_accepts_repeated_args_after_subcommand() {
+        let cli = WorkflowCli::parse_from(["wf", "file", "deploy.yaml", "--arg", "a=1", "--arg", "b=2", "--no-prompt"]);
+        assert_eq!(cli.args, vec![("a".to_string(), "1".to_string()), ("b".to_string(), "2".to_string())]);
+        assert!(cli.no_prompt);
+    }
+
+    #[test]
+    fn cli_accepts_output_only_alias_for_print() {
+        assert!(WorkflowCli::parse_from(["wf", "--output-only"]).print);
+        assert!(WorkflowCli::parse_from(["wf", "run", "deploy", "--print"]).print);
+    }
+
+    #[test]
+    fn cli_parses_shell_init_and_shell_filter() {
+        let cli = WorkflowCli::parse_from(["wf", "shell-init", "zsh", "--key", "^W"]);
+        let Some(WorkflowCliCommand::ShellInit { shell, key }) = cli.command else { panic!("expected shell-init") };
+        assert_eq!(shell, Shell::Zsh);
+        assert_eq!(key.as_deref(), Some("^W"));
+
+        let cli = WorkflowCli::parse_from(["wf", "--print", "--shell", "fish"]);
+        assert_eq!(cli.shell, Some(Shell::Fish));
+    }
+}
@@ -11,8 +11,16 @@ pub enum ValidationError {
     SelectionFailed(String, String),
     InputFailed(String, String),
     EnumMissingConfig(String),
-    DynamicResolutionFailed(String),
     NoOptionsFound(String),
+    UnknownArgument(String),
+    DuplicateArgument(String),
+    MissingArgument(String),
+    ReservedArgumentName(String),
+    InvalidCondition(String, String),
+    ComputedMissingExpression(String),
+    UnknownDependency(String, String),
+    DependencyCycle(String),
+    UnknownFields(String),
     Other(String)
 }
 
@@ -32,12 +40,36 @@ impl fmt::Display for ValidationError {
             Self::EnumMissingConfig(name) => {
                 write!(f, "{}", t_params!("error_enum_argument_missing_configuration", &[name]))
             }
-            Self::DynamicResolutionFailed(name) => {
-                write!(f, "{}", t_params!("error_dynamic_resolution_failed", &[name]))
-            }
             Self::NoOptionsFound(name) => {
                 write!(f, "{}", t_params!("error_no_options_found", &[name]))
             }
+            Self::UnknownArgument(name) => {
+                write!(f, "{}", t_params!("error_unknown_argument", &[name]))
+            }
+            Self::DuplicateArgument(name) => {
+                write!(f, "{}", t_params!("error_duplicate_argument", &[name]))
+            }
+            Self::MissingArgument(name) => {
+                write!(f, "{}", t_params!("error_missing_argument", &[name]))
+            }
+            Self::ReservedArgumentName(name) => {
+                write!(f, "{}", t_params!("error_reserved_argument_name", &[name]))
+            }
+            Self::InvalidCondition(name, msg) => {
+                write!(f, "{}", t_params!("error_invalid_condition", &[name, msg]))
+            }
+            Self::ComputedMissingExpression(name) => {
+                write!(f, "{}", t_params!("error_computed_argument_missing_expression", &[name]))
+            }
+            Self::UnknownDependency(name, dependency) => {
+                write!(f, "{}", t_params!("error_unknown_dependency", &[name, dependency]))
+            }
+            Self::DependencyCycle(cycle) => {
+                write!(f, "{}", t_params!("error_dependency_cycle", &[cycle]))
+            }
+            Self::UnknownFields(fields) => {
+                write!(f, "{}", t_params!("error_unknown_workflow_fields", &[fields]))
+            }
             Self::Other(msg) => write!(f, "{}", t_params!("error_validation", &[msg]))
         }
     }
@@ -214,6 +246,9 @@ mod tests {
     fn all_variants_display_without_panic() {
         let variants: Vec<WorkflowError> = vec![
             ValidationError::Other("bad".to_string()).into(),
+            ValidationError::UnknownArgument("port".to_string()).into(),
+            ValidationError::DuplicateArgument("port".to_string()).into(),
+            ValidationError::MissingArgument("port".to_string()).into(),
             StorageError::Io("disk".to_string()).into(),
             PromptError::Interaction("cancelled".to_string()).into(),
             WorkflowError::Execution("failed".to_string()),
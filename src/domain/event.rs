@@ -9,7 +9,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::domain::workflow::Workflow;
+use crate::domain::workflow::{DiscoveryFailure, StepRecord, Workflow, WorkflowContext};
 
 /// Serializable event data for storage
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -82,6 +82,14 @@ pub struct WorkflowDiscoveredEvent {
     pub file_path: String
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Workflow discovery failed event - emitted for each workflow file that could not be loaded
+pub struct WorkflowDiscoveryFailedEvent {
+    pub event_id:  String,
+    pub timestamp: DateTime<Utc>,
+    pub failure:   DiscoveryFailure
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// Workflow selection event - emitted when a workflow is selected for execution
 pub struct WorkflowSelectedEvent {
@@ -106,7 +114,42 @@ pub struct WorkflowStartedEvent {
 pub struct WorkflowArgumentsResolvedEvent {
     pub event_id:  String,
     pub timestamp: DateTime<Utc>,
-    pub arguments: HashMap<String, String>
+    pub arguments: HashMap<String, String>,
+    /// Context the commands are rendered with, so a replay shows what ran; none for sessions
+    /// recorded before it was kept
+    #[serde(default)]
+    pub context:   Option<WorkflowContext>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Workflow executed event - emitted when the rendered command ran and exited with status 0
+pub struct WorkflowExecutedEvent {
+    pub event_id:    String,
+    pub timestamp:   DateTime<Utc>,
+    pub command:     String,
+    pub exit_code:   i32,
+    pub duration_ms: u64
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Workflow execution failed event - emitted when the rendered command could not be started,
+/// exited non-zero or was killed by a signal (no exit code)
+pub struct WorkflowExecutionFailedEvent {
+    pub event_id:    String,
+    pub timestamp:   DateTime<Utc>,
+    pub command:     String,
+    pub exit_code:   Option<i32>,
+    pub duration_ms: u64,
+    pub error:       Option<String>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Workflow step completed event - emitted for each step of a multi-step workflow that was
+/// copied, executed or skipped
+pub struct WorkflowStepCompletedEvent {
+    pub event_id:  String,
+    pub timestamp: DateTime<Utc>,
+    pub step:      StepRecord
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -126,9 +169,13 @@ pub struct WorkflowCompletedEvent {
 pub enum WorkflowEvent {
     /// Common Events
     WorkflowDiscovered(WorkflowDiscoveredEvent),
+    WorkflowDiscoveryFailed(WorkflowDiscoveryFailedEvent),
     WorkflowSelected(WorkflowSelectedEvent),
     WorkflowStarted(WorkflowStartedEvent),
     WorkflowArgumentsResolved(WorkflowArgumentsResolvedEvent),
+    WorkflowExecuted(WorkflowExecutedEvent),
+    WorkflowExecutionFailed(WorkflowExecutionFailedEvent),
+    WorkflowStepCompleted(WorkflowStepCompletedEvent),
     WorkflowCompleted(WorkflowCompletedEvent),
 
     ///  Command Specific Events
@@ -147,9 +194,13 @@ impl Display for WorkflowEvent {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let event_type = match self {
             WorkflowEvent::WorkflowDiscovered(_) => "WorkflowDiscovered",
+            WorkflowEvent::WorkflowDiscoveryFailed(_) => "WorkflowDiscoveryFailed",
             WorkflowEvent::WorkflowSelected(_) => "WorkflowSelected",
             WorkflowEvent::WorkflowStarted(_) => "WorkflowStarted",
             WorkflowEvent::WorkflowArgumentsResolved(_) => "WorkflowArgumentsResolved",
+            WorkflowEvent::WorkflowExecuted(_) => "WorkflowExecuted",
+            WorkflowEvent::WorkflowExecutionFailed(_) => "WorkflowExecutionFailed",
+            WorkflowEvent::WorkflowStepCompleted(_) => "WorkflowStepCompleted",
             WorkflowEvent::WorkflowCompleted(_) => "WorkflowCompleted",
             WorkflowEvent::AvailableWorkflowsListed(_) => "AvailableWorkflowsListed",
             WorkflowEvent::SyncRequested(_) => "SyncRequested",
@@ -176,7 +227,10 @@ pub struct SyncRequestedEvent {
     pub timestamp:  DateTime<Utc>,
     pub remote_url: String,
     pub branch:     String,
-    pub ssh_key:    Option<String>
+    pub ssh_key:    Option<String>,
+    /// Name of the configured remote; none for syncs recorded before remotes had names
+    #[serde(default)]
+    pub remote:     Option<String>
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -214,6 +268,7 @@ pub struct AggregateReplayedEvent {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::workflow::StepAction;
 
     fn test_workflow() -> Workflow {
         Workflow {
@@ -225,7 +280,11 @@ mod tests {
             source_url:  None,
             author:      None,
             author_url:  None,
-            shells:      vec![]
+            execute:     false,
+            steps:       vec![],
+            shells:      vec![],
+            namespace:   None,
+            origin:      None
         }
     }
 
@@ -245,6 +304,19 @@ mod tests {
                 }),
                 "WorkflowDiscovered"
             ),
+            (
+                WorkflowEvent::WorkflowDiscoveryFailed(WorkflowDiscoveryFailedEvent {
+                    event_id:  id.clone(),
+                    timestamp: ts,
+                    failure:   DiscoveryFailure {
+                        path:    "bad.yaml".to_string(),
+                        line:    Some(1),
+                        column:  Some(2),
+                        message: "m".to_string()
+                    }
+                }),
+                "WorkflowDiscoveryFailed"
+            ),
             (
                 WorkflowEvent::WorkflowSelected(WorkflowSelectedEvent {
                     event_id:  id.clone(),
@@ -268,10 +340,48 @@ mod tests {
                 WorkflowEvent::WorkflowArgumentsResolved(WorkflowArgumentsResolvedEvent {
                     event_id:  id.clone(),
                     timestamp: ts,
-                    arguments: HashMap::new()
+                    arguments: HashMap::new(),
+                    context:   None
                 }),
                 "WorkflowArgumentsResolved"
             ),
+            (
+                WorkflowEvent::WorkflowExecuted(WorkflowExecutedEvent {
+                    event_id:    id.clone(),
+                    timestamp:   ts,
+                    command:     "c".to_string(),
+                    exit_code:   0,
+                    duration_ms: 1
+                }),
+                "WorkflowExecuted"
+            ),
+            (
+                WorkflowEvent::WorkflowExecutionFailed(WorkflowExecutionFailedEvent {
+                    event_id:    id.clone(),
+                    timestamp:   ts,
+                    command:     "c".to_string(),
+                    exit_code:   Some(1),
+                    duration_ms: 1,
+                    error:       None
+                }),
+                "WorkflowExecutionFailed"
+            ),
+            (
+                WorkflowEvent::WorkflowStepCompleted(WorkflowStepCompletedEvent {
+                    event_id:  id.clone(),
+                    timestamp: ts,
+                    step:      StepRecord {
+                        index:       0,
+                        name:        "s".to_string(),
+                        command:     "c".to_string(),
+                        action:      StepAction::Skipped,
+                        exit_code:   None,
+                        duration_ms: None,
+                        error:       None
+                    }
+                }),
+                "WorkflowStepCompleted"
+            ),
             (
                 WorkflowEvent::WorkflowCompleted(WorkflowCompletedEvent { event_id: id.clone(), timestamp: ts }),
                 "WorkflowCompleted"
@@ -290,7 +400,8 @@ mod tests {
                     timestamp:  ts,
                     remote_url: "r".to_string(),
                     branch:     "b".to_string(),
-                    ssh_key:    None
+                    ssh_key:    None,
+                    remote:     None
                 }),
                 "SyncRequested"
             ),
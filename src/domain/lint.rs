@@ -0,0 +1,81 @@
+//! Findings of `wf lint`
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// How bad a finding is: errors fail the lint, warnings only with `--strict`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning
+}
+
+impl Display for LintSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintSeverity::Error => write!(f, "error"),
+            LintSeverity::Warning => write!(f, "warning")
+        }
+    }
+}
+
+/// A problem found in a workflow file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintIssue {
+    /// The file at fault
+    pub path:     String,
+    /// Position of a YAML error, starting at 1
+    pub line:     Option<usize>,
+    pub column:   Option<usize>,
+    /// Workflow name, when the file could be parsed
+    pub workflow: Option<String>,
+    /// Argument the finding is about
+    pub argument: Option<String>,
+    pub severity: LintSeverity,
+    /// Stable identifier of the check, e.g. `unused-argument`
+    pub code:     String,
+    /// Localized explanation
+    pub message:  String
+}
+
+impl Display for LintIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path)?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+        }
+        write!(f, ": {}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
+
+/// Everything `wf lint` found in the files it checked
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LintReport {
+    /// Number of workflow files checked
+    pub files:  usize,
+    pub issues: Vec<LintIssue>
+}
+
+impl LintReport {
+    pub fn errors(&self) -> usize {
+        self.count(LintSeverity::Error)
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.count(LintSeverity::Warning)
+    }
+
+    /// Whether the lint fails: on any error, or on any finding at all when `strict`
+    pub fn failed(&self, strict: bool) -> bool {
+        self.errors() > 0 || (strict && !self.issues.is_empty())
+    }
+
+    fn count(&self, severity: LintSeverity) -> usize {
+        self.issues.iter().filter(|issue| issue.severity == severity).count()
+    }
+}
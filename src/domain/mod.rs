@@ -3,5 +3,7 @@ pub mod constant;
 pub mod engine;
 pub mod error;
 pub mod event;
+pub mod lint;
+pub mod shell;
 pub mod state;
 pub mod workflow;
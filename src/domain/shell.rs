@@ -0,0 +1,165 @@
+//! Interactive shells wf integrates with
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::workflow::QuoteStyle;
+
+/// Shells with a line-editor widget (`wf shell-init <shell>`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum)]
+pub enum Shell {
+    Zsh,
+    Bash,
+    Fish
+}
+
+impl Shell {
+    /// Name as used in a workflow's `shells` list
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shell::Zsh => "zsh",
+            Shell::Bash => "bash",
+            Shell::Fish => "fish"
+        }
+    }
+
+    /// Key the widget is bound to unless overridden, written in the shell's own notation (Ctrl-G)
+    pub fn default_key(&self) -> &'static str {
+        match self {
+            Shell::Zsh => "^G",
+            Shell::Bash => "\\C-g",
+            Shell::Fish => "\\cg"
+        }
+    }
+}
+
+impl Display for Shell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Quoting rules of a shell family
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShellDialect {
+    /// sh, bash, zsh, ksh, dash
+    #[default]
+    Posix,
+    Fish,
+    /// pwsh / powershell
+    PowerShell
+}
+
+impl ShellDialect {
+    /// Dialect for a shell name as written in a workflow's `shells` list. Unknown shells are
+    /// treated as POSIX.
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "fish" => ShellDialect::Fish,
+            "pwsh" | "powershell" => ShellDialect::PowerShell,
+            _ => ShellDialect::Posix
+        }
+    }
+
+    /// Quote `value` according to `style`
+    pub fn quote(&self, value: &str, style: QuoteStyle) -> String {
+        match style {
+            QuoteStyle::Raw => value.to_string(),
+            QuoteStyle::Auto if self.is_safe(value) => value.to_string(),
+            QuoteStyle::Auto | QuoteStyle::Single => self.single_quote(value),
+            QuoteStyle::Double => self.double_quote(value)
+        }
+    }
+
+    /// Escape `value` for the inside of a `'...'` or `"..."` pair the template already has,
+    /// `quote` being that pair's character
+    pub fn escape_within(&self, value: &str, quote: char) -> String {
+        let quoted = if quote == '"' { self.double_quote(value) } else { self.single_quote(value) };
+        quoted[1..quoted.len() - 1].to_string()
+    }
+
+    /// Whether `value` reads as a single literal word without quoting
+    fn is_safe(&self, value: &str) -> bool {
+        !value.is_empty()
+            && value.chars().all(|c| {
+                c.is_ascii_alphanumeric()
+                    || matches!(c, '_' | '-' | '.' | '/' | ':' | '=' | '+' | '%')
+                    || (matches!(c, '@' | ',') && *self != ShellDialect::PowerShell)
+            })
+    }
+
+    fn single_quote(&self, value: &str) -> String {
+        match self {
+            ShellDialect::Posix => format!("'{}'", value.replace('\'', "'\\''")),
+            ShellDialect::Fish => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'")),
+            ShellDialect::PowerShell => format!("'{}'", value.replace('\'', "''"))
+        }
+    }
+
+    fn double_quote(&self, value: &str) -> String {
+        let mut quoted = String::with_capacity(value.len() + 2);
+        quoted.push('"');
+        for c in value.chars() {
+            match (self, c) {
+                (ShellDialect::Posix, '\\' | '"' | '$' | '`') | (ShellDialect::Fish, '\\' | '"' | '$') => {
+                    quoted.push('\\')
+                }
+                (ShellDialect::PowerShell, '`' | '"' | '$') => quoted.push('`'),
+                _ => {}
+            }
+            quoted.push(c);
+        }
+        quoted.push('"');
+        quoted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_leaves_plain_words_alone() {
+        assert_eq!(ShellDialect::Posix.quote("prod-eu_1.yaml", QuoteStyle::Auto), "prod-eu_1.yaml");
+        assert_eq!(ShellDialect::Posix.quote("", QuoteStyle::Auto), "''");
+    }
+
+    #[test]
+    fn posix_single_quotes_escape_embedded_quotes() {
+        assert_eq!(ShellDialect::Posix.quote("it's; rm -rf /", QuoteStyle::Auto), r"'it'\''s; rm -rf /'");
+    }
+
+    #[test]
+    fn posix_double_quotes_escape_expansions() {
+        assert_eq!(
+            ShellDialect::Posix.quote(r#"a "b" $HOME `x` \"#, QuoteStyle::Double),
+            r#""a \"b\" \$HOME \`x\` \\""#
+        );
+    }
+
+    #[test]
+    fn fish_single_quotes_escape_backslash_and_quote() {
+        assert_eq!(ShellDialect::Fish.quote(r"it's \n", QuoteStyle::Single), r"'it\'s \\n'");
+        assert_eq!(ShellDialect::Fish.quote("$x", QuoteStyle::Double), "\"\\$x\"");
+    }
+
+    #[test]
+    fn powershell_uses_doubled_quotes_and_backticks() {
+        assert_eq!(ShellDialect::PowerShell.quote("it's", QuoteStyle::Single), "'it''s'");
+        assert_eq!(ShellDialect::PowerShell.quote("$env:HOME", QuoteStyle::Double), "\"`$env:HOME\"");
+        assert_eq!(ShellDialect::PowerShell.quote("a,b", QuoteStyle::Auto), "'a,b'");
+    }
+
+    #[test]
+    fn escape_within_leaves_out_the_surrounding_quotes() {
+        assert_eq!(ShellDialect::Posix.escape_within("it's", '\''), r"it'\''s");
+        assert_eq!(ShellDialect::Posix.escape_within(r#"say "$x""#, '"'), r#"say \"\$x\""#);
+        assert_eq!(ShellDialect::PowerShell.escape_within("it's", '\''), "it''s");
+    }
+
+    #[test]
+    fn raw_inserts_value_verbatim() {
+        assert_eq!(ShellDialect::Posix.quote("$(whoami)", QuoteStyle::Raw), "$(whoami)");
+    }
+}
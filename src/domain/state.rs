@@ -9,7 +9,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tabled::Tabled;
 
-use crate::domain::workflow::Workflow;
+use crate::domain::workflow::{StepAction, StepRecord, Workflow};
 
 /// Trait for displaying workflow state in a table format
 /// This trait enforces that each state provides display information without polluting the state
@@ -47,6 +47,12 @@ pub enum WorkflowState {
     WorkflowStarted(WorkflowStartedState),
     /// Workflow arguments have been resolved
     WorkflowArgumentsResolved(WorkflowArgumentsResolvedState),
+    /// The rendered command ran and succeeded
+    WorkflowExecuted(WorkflowExecutedState),
+    /// The rendered command failed to start or exited unsuccessfully
+    WorkflowExecutionFailed(WorkflowExecutionFailedState),
+    /// One or more steps of a multi-step workflow have been handled
+    WorkflowStepCompleted(WorkflowStepCompletedState),
     /// Workflow execution has completed
     WorkflowCompleted(WorkflowCompletedState),
     /// Workflows have been synced from git repository
@@ -75,12 +81,15 @@ pub struct SyncRequestedState {
     /// Branch that was synced
     pub branch:     String,
     /// SSH key that was used
-    pub ssh_key:    Option<String>
+    pub ssh_key:    Option<String>,
+    /// Name of the configured remote being synced
+    #[serde(default)]
+    pub remote:     Option<String>
 }
 
 impl SyncRequestedState {
-    pub fn new(remote_url: String, branch: String, ssh_key: Option<String>) -> Self {
-        Self { remote_url, branch, ssh_key }
+    pub fn new(remote_url: String, branch: String, ssh_key: Option<String>, remote: Option<String>) -> Self {
+        Self { remote_url, branch, ssh_key, remote }
     }
 }
 
@@ -174,6 +183,68 @@ impl WorkflowArgumentsResolvedState {
     }
 }
 
+/// State after the rendered command ran successfully
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowExecutedState {
+    /// All workflows discovered from the filesystem
+    pub discovered_workflows: Vec<Workflow>,
+    /// The workflow that was executed
+    pub selected_workflow:    Workflow,
+    /// Unique execution ID for this workflow run
+    pub execution_id:         String,
+    /// Resolved arguments the command was rendered with
+    pub resolved_arguments:   HashMap<String, String>,
+    /// The command that ran
+    pub command:              String,
+    /// Process exit code
+    pub exit_code:            i32,
+    /// Wall-clock run time in milliseconds
+    pub duration_ms:          u64
+}
+
+/// State after the rendered command failed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowExecutionFailedState {
+    /// All workflows discovered from the filesystem
+    pub discovered_workflows: Vec<Workflow>,
+    /// The workflow that was executed
+    pub selected_workflow:    Workflow,
+    /// Unique execution ID for this workflow run
+    pub execution_id:         String,
+    /// Resolved arguments the command was rendered with
+    pub resolved_arguments:   HashMap<String, String>,
+    /// The command that ran
+    pub command:              String,
+    /// Process exit code, if the process exited on its own
+    pub exit_code:            Option<i32>,
+    /// Wall-clock run time in milliseconds
+    pub duration_ms:          u64,
+    /// Why the process could not be started, if it never ran
+    pub error:                Option<String>
+}
+
+/// State after steps of a multi-step workflow were copied, executed or skipped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowStepCompletedState {
+    /// All workflows discovered from the filesystem
+    pub discovered_workflows: Vec<Workflow>,
+    /// The workflow whose steps are running
+    pub selected_workflow:    Workflow,
+    /// Unique execution ID for this workflow run
+    pub execution_id:         String,
+    /// Resolved arguments the steps were rendered with
+    pub resolved_arguments:   HashMap<String, String>,
+    /// Steps handled so far, in order
+    pub steps:                Vec<StepRecord>
+}
+
+impl WorkflowStepCompletedState {
+    /// Whether the last handled step was executed and failed
+    pub fn failed(&self) -> bool {
+        self.steps.last().is_some_and(StepRecord::failed)
+    }
+}
+
 /// State after workflow execution has completed
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowCompletedState {
@@ -313,6 +384,70 @@ impl StateDisplay for WorkflowArgumentsResolvedState {
     }
 }
 
+impl StateDisplay for WorkflowExecutedState {
+    fn phase_name(&self) -> String {
+        crate::t!("state_phase_workflow_executed").to_string()
+    }
+
+    fn table_rows(&self) -> Vec<(String, String)> {
+        vec![
+            (crate::t!("state_field_workflow").to_string(), self.selected_workflow.name.clone()),
+            (crate::t!("state_field_execution_id").to_string(), self.execution_id.clone()),
+            (crate::t!("state_field_command").to_string(), self.command.clone()),
+            (crate::t!("state_field_exit_code").to_string(), self.exit_code.to_string()),
+            (crate::t!("state_field_duration").to_string(), format!("{} ms", self.duration_ms)),
+        ]
+    }
+}
+
+impl StateDisplay for WorkflowExecutionFailedState {
+    fn phase_name(&self) -> String {
+        crate::t!("state_phase_workflow_execution_failed").to_string()
+    }
+
+    fn table_rows(&self) -> Vec<(String, String)> {
+        let mut rows = vec![
+            (crate::t!("state_field_workflow").to_string(), self.selected_workflow.name.clone()),
+            (crate::t!("state_field_execution_id").to_string(), self.execution_id.clone()),
+            (crate::t!("state_field_command").to_string(), self.command.clone()),
+            (
+                crate::t!("state_field_exit_code").to_string(),
+                self.exit_code.map(|code| code.to_string()).unwrap_or_else(|| "-".to_string())
+            ),
+            (crate::t!("state_field_duration").to_string(), format!("{} ms", self.duration_ms)),
+        ];
+        if let Some(error) = &self.error {
+            rows.push((crate::t!("state_field_error").to_string(), error.clone()));
+        }
+        rows
+    }
+}
+
+impl StateDisplay for WorkflowStepCompletedState {
+    fn phase_name(&self) -> String {
+        crate::t!("state_phase_workflow_step_completed").to_string()
+    }
+
+    fn table_rows(&self) -> Vec<(String, String)> {
+        let mut rows = vec![
+            (crate::t!("state_field_workflow").to_string(), self.selected_workflow.name.clone()),
+            (crate::t!("state_field_execution_id").to_string(), self.execution_id.clone()),
+        ];
+        for step in &self.steps {
+            let outcome = match (step.action, step.exit_code) {
+                (StepAction::Copied, _) => crate::t!("step_copied").to_string(),
+                (StepAction::Skipped, _) => crate::t!("step_skipped").to_string(),
+                (StepAction::Executed, Some(exit_code)) => {
+                    crate::t_params!("step_exited", &[&exit_code.to_string()]).to_string()
+                }
+                (StepAction::Executed, None) => step.error.clone().unwrap_or_else(|| "-".to_string())
+            };
+            rows.push((format!("{}. {}", step.index + 1, step.name), format!("{} ({})", step.command, outcome)));
+        }
+        rows
+    }
+}
+
 impl StateDisplay for WorkflowCompletedState {
     fn phase_name(&self) -> String {
         crate::t!("state_phase_workflow_completed").to_string()
@@ -340,6 +475,9 @@ impl StateDisplay for SyncRequestedState {
             (crate::t!("state_field_remote_url").to_string(), self.remote_url.clone()),
             (crate::t!("state_field_branch").to_string(), self.branch.clone()),
         ];
+        if let Some(remote) = &self.remote {
+            rows.push((crate::t!("state_field_remote").to_string(), remote.clone()));
+        }
         if self.ssh_key.is_some() {
             rows.push((crate::t!("state_field_ssh_key").to_string(), "****** (hidden)".to_string()));
         }
@@ -388,6 +526,9 @@ impl StateDisplay for WorkflowState {
             WorkflowState::WorkflowSelected(s) => s.phase_name(),
             WorkflowState::WorkflowStarted(s) => s.phase_name(),
             WorkflowState::WorkflowArgumentsResolved(s) => s.phase_name(),
+            WorkflowState::WorkflowExecuted(s) => s.phase_name(),
+            WorkflowState::WorkflowExecutionFailed(s) => s.phase_name(),
+            WorkflowState::WorkflowStepCompleted(s) => s.phase_name(),
             WorkflowState::WorkflowCompleted(s) => s.phase_name(),
             WorkflowState::SyncRequested(s) => s.phase_name(),
             WorkflowState::WorkflowsSynced(s) => s.phase_name(),
@@ -403,6 +544,9 @@ impl StateDisplay for WorkflowState {
             WorkflowState::WorkflowSelected(s) => s.table_rows(),
             WorkflowState::WorkflowStarted(s) => s.table_rows(),
             WorkflowState::WorkflowArgumentsResolved(s) => s.table_rows(),
+            WorkflowState::WorkflowExecuted(s) => s.table_rows(),
+            WorkflowState::WorkflowExecutionFailed(s) => s.table_rows(),
+            WorkflowState::WorkflowStepCompleted(s) => s.table_rows(),
             WorkflowState::WorkflowCompleted(s) => s.table_rows(),
             WorkflowState::SyncRequested(s) => s.table_rows(),
             WorkflowState::WorkflowsSynced(s) => s.table_rows(),
@@ -3,12 +3,28 @@
 //! This module contains the core domain types for workflows including
 //! workflow definitions, arguments, and related functionality.
 
-use std::{collections::HashMap, fmt::Display, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    path::{Path, PathBuf},
+    sync::OnceLock
+};
 
 use chrono::{DateTime, Utc};
+use regex::Regex;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::{
+    domain::{
+        error::ValidationError,
+        shell::{Shell, ShellDialect}
+    },
+    port::template::TemplateInspector,
+    t, t_params
+};
+
 /// Represents a complete workflow definition parsed from YAML.
 ///
 /// A workflow contains metadata, a command template with placeholder variables,
@@ -26,12 +42,23 @@ use uuid::Uuid;
 ///     default_value: "Hello World"
 /// tags: ["example"]
 /// shells: ["bash", "zsh"]
+/// execute: false  # Optional, run the command instead of copying it
+/// ```
+///
+/// Instead of `command`, a workflow can list `steps` that share its arguments:
+/// ```yaml
+/// steps:
+///   - name: "Forward"
+///     command: "kubectl port-forward svc/{{service}} 8080:80 &"
+///   - name: "Probe"
+///     command: "curl -s localhost:8080/health"
 /// ```
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Workflow {
     /// Human-readable name of the workflow
     pub name:        String,
-    /// Command template with {{variable}} placeholders to be executed
+    /// Command template with {{variable}} placeholders to be executed (empty when `steps` is used)
+    #[serde(default)]
     pub command:     String,
     /// Description explaining what the workflow does
     pub description: String,
@@ -46,7 +73,161 @@ pub struct Workflow {
     /// Optional URL to author's profile/website
     pub author_url:  Option<String>,
     /// List of supported shell environments
-    pub shells:      Vec<String>
+    pub shells:      Vec<String>,
+    /// Run the rendered command instead of copying it (same as `--exec`)
+    #[serde(default)]
+    pub execute:     bool,
+    /// Ordered commands sharing the workflow's arguments, used instead of `command`
+    #[serde(default)]
+    pub steps:       Vec<WorkflowStep>,
+    /// Folder the file was discovered in, relative to the workflows directory (e.g. `k8s`).
+    /// Set by discovery from the file's location; the loader drops it from workflow files, and
+    /// only stored events bring it back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub namespace:   Option<String>,
+    /// Search path directory the file was discovered in. Set by discovery, like `namespace`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub origin:      Option<WorkflowOrigin>
+}
+
+/// Fields of [`Workflow`] that discovery sets and workflow files can't
+pub const DISCOVERY_FIELDS: [&str; 2] = ["namespace", "origin"];
+
+impl Workflow {
+    /// URL/shell friendly form of the workflow name, e.g. "Deploy to K8s" -> "deploy-to-k8s"
+    pub fn slug(&self) -> String {
+        slugify(&self.name)
+    }
+
+    /// Slug prefixed with the namespace, e.g. "k8s/tail-logs"; just the slug at the top level
+    pub fn qualified_slug(&self) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}/{}", namespace, self.slug()),
+            None => self.slug()
+        }
+    }
+
+    /// Directory holding the workflow file, when it was discovered on the search path
+    pub fn directory(&self) -> Option<PathBuf> {
+        let origin = self.origin.as_ref()?;
+        Some(match &self.namespace {
+            Some(namespace) => origin.dir.join(namespace),
+            None => origin.dir.clone()
+        })
+    }
+
+    /// Whether the workflow is meant for `shell`. An empty `shells` list means any shell.
+    pub fn supports_shell(&self, shell: Shell) -> bool {
+        self.shells.is_empty() || self.shells.iter().any(|s| s.eq_ignore_ascii_case(shell.name()))
+    }
+
+    /// Whether the workflow runs a list of `steps` rather than a single `command`
+    pub fn is_multi_step(&self) -> bool {
+        !self.steps.is_empty()
+    }
+
+    /// Quoting rules to render with, taken from the first entry in `shells` (POSIX when empty)
+    pub fn dialect(&self) -> ShellDialect {
+        self.shells.first().map(|shell| ShellDialect::from_name(shell)).unwrap_or_default()
+    }
+}
+
+/// One named command of a multi-step workflow
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct WorkflowStep {
+    /// Label shown when the step comes up
+    pub name:    String,
+    /// Command template, rendered with the workflow's arguments
+    pub command: String
+}
+
+/// What was done with a step during a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepAction {
+    /// Handed to the clipboard or stdout
+    Copied,
+    /// Run in the user's shell
+    Executed,
+    /// Passed over by the user
+    Skipped
+}
+
+/// Record of one step of a multi-step run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepRecord {
+    /// Position in `steps`, starting at 0
+    pub index:       usize,
+    pub name:        String,
+    /// The rendered command
+    pub command:     String,
+    pub action:      StepAction,
+    /// Exit code of an executed step, `None` when killed by a signal or never started
+    pub exit_code:   Option<i32>,
+    pub duration_ms: Option<u64>,
+    /// Why an executed step could not be started
+    pub error:       Option<String>
+}
+
+impl StepRecord {
+    /// Whether the step was executed without exiting successfully
+    pub fn failed(&self) -> bool {
+        self.action == StepAction::Executed && self.exit_code != Some(0)
+    }
+}
+
+/// A directory on the workflow search path
+///
+/// Discovery looks in `.wf/` and `.workflows/` from the working directory upwards, then in the
+/// global workflows directory, then in the `WF_PATH` entries. Earlier directories shadow later
+/// ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowOrigin {
+    pub dir:   PathBuf,
+    /// How the chooser shows it, e.g. `./.wf`, `../.workflows` or `global`
+    pub label: String
+}
+
+/// A workflow file that could not be loaded during discovery
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscoveryFailure {
+    /// The file with the problem, which may be an `extends` base or argument fragment
+    pub path:    String,
+    /// Position of a YAML error, starting at 1
+    pub line:    Option<usize>,
+    pub column:  Option<usize>,
+    pub message: String
+}
+
+impl Display for DiscoveryFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path)?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Template variables injected from the [`WorkflowContext`]; arguments can't use these names
+pub const RESERVED_ARGUMENT_NAMES: &[&str] = &["ctx", "env"];
+
+/// Lowercase a string and collapse every run of non-alphanumeric characters into a single `-`.
+pub fn slugify(input: &str) -> String {
+    let mut slug = String::with_capacity(input.len());
+    for c in input.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
 }
 
 impl Display for Workflow {
@@ -80,8 +261,45 @@ impl Display for Workflow {
 /// - name: filename
 ///   description: "File to process"
 ///   default_value: ~  # ~ means null/no default
+///
+/// # Value inserted verbatim instead of shell-quoted
+/// - name: flags
+///   description: "Extra flags"
+///   quote: none  # auto (default for Text), none, single or double
+///
+/// # Masked input, redacted in the session history
+/// - name: token
+///   arg_type: Secret
+///   description: "API token"
+///
+/// # File picked from the filesystem
+/// - name: manifest
+///   arg_type: Path
+///   description: "Manifest file"
+///   path:
+///     must_exist: true
+///     kind: file
+///     glob: "*.yaml"
+///
+/// # Only asked for when an earlier argument has a certain value
+/// - name: replicas
+///   arg_type: Number
+///   description: "Replica count"
+///   when: 'action == "scale"'
+///
+/// # Derived from other arguments, never asked for
+/// - name: image
+///   arg_type: Computed
+///   description: "Image reference"
+///   expression: "{{ registry }}/{{ service }}:{{ tag }}"
+///
+/// # Value checked before it is accepted
+/// - name: ticket
+///   description: "Ticket key"
+///   validation:
+///     pattern: "^[A-Z]+-[0-9]+$"
 /// ```
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct WorkflowArgument {
     /// Variable name used in command template (e.g., {{name}})
     pub name:               String,
@@ -94,19 +312,298 @@ pub struct WorkflowArgument {
     pub default_value:      Option<String>,
     /// For Enum type: identifier for the dynamic option set
     pub enum_name:          Option<String>,
-    /// For Enum type: command to execute to get available options
+    /// For Enum type: command to execute to get available options, rendered with the values
+    /// resolved before it
     pub enum_command:       Option<String>,
     /// For Enum type: static list of predefined options
     pub enum_variants:      Option<Vec<String>>,
-    /// For Enum type: name of the argument to reference for dynamic resolution in enum_command
+    /// For Enum type: argument enum_command needs first; same as listing it in `depends_on`
     pub dynamic_resolution: Option<String>,
+    /// Arguments resolved before this one, whatever their place in the file
+    #[serde(default)]
+    pub depends_on:         Vec<String>,
     /// For Enum: enable multi-select
     #[serde(default)]
     pub multi:              bool,
     /// For Enum with multi: minimum number of selections
     pub min_selections:     Option<usize>,
     /// For Enum with multi: maximum number of selections
-    pub max_selections:     Option<usize>
+    pub max_selections:     Option<usize>,
+    /// How the value is quoted when rendered into the command (defaults to `auto` for Text)
+    #[serde(default)]
+    pub quote:              Option<QuoteStyle>,
+    /// Rules the value must satisfy, whether typed in or passed with `--arg`
+    #[serde(default)]
+    pub validation:         Option<ArgumentValidation>,
+    /// For Path: what kind of path is accepted and where relative paths start
+    #[serde(default)]
+    pub path:               Option<PathOptions>,
+    /// Condition on the arguments resolved before this one (e.g. `action == "scale"`); when it is
+    /// false the argument is skipped and renders empty
+    #[serde(default)]
+    pub when:               Option<String>,
+    /// For Computed: template rendered from the arguments resolved before it and the session
+    /// context
+    #[serde(default)]
+    pub expression:         Option<String>
+}
+
+impl WorkflowArgument {
+    /// Effective quoting: the explicit `quote`, else `auto` for Text, Secret, Path and Computed
+    /// and `none` for everything else
+    pub fn quote_style(&self) -> QuoteStyle {
+        self.quote.unwrap_or(match self.arg_type {
+            ArgumentType::Text | ArgumentType::Secret | ArgumentType::Path | ArgumentType::Computed => QuoteStyle::Auto,
+            _ => QuoteStyle::Raw
+        })
+    }
+
+    /// Arguments to resolve before this one: `depends_on` and `dynamic_resolution`
+    pub fn dependencies(&self) -> impl Iterator<Item = &str> {
+        self.depends_on.iter().chain(&self.dynamic_resolution).map(String::as_str)
+    }
+
+    /// Variables the `enum_command` and `expression` templates and the `when` condition use.
+    /// These name arguments too, unless they are context variables. Templates that don't parse
+    /// use none; `wf lint` and rendering report them.
+    pub fn template_references(&self, inspector: &dyn TemplateInspector) -> HashSet<String> {
+        let templates = [&self.enum_command, &self.expression].into_iter().flatten().map(|t| inspector.variables(t));
+        let condition = self.when.as_deref().map(|when| inspector.condition_variables(when));
+        templates.chain(condition).filter_map(Result::ok).flatten().filter(|variable| variable != &self.name).collect()
+    }
+
+    /// Check a typed or provided value: numbers must parse, then the `validation` rules apply.
+    /// The error is a localized message meant to be shown next to the input; it never repeats the
+    /// value of a `Secret` argument.
+    pub fn check_value(&self, value: &str) -> Result<(), String> {
+        let concealed = self.arg_type == ArgumentType::Secret;
+        if self.arg_type == ArgumentType::Number && value.trim().parse::<f64>().is_err() {
+            return Err(invalid_number(value, concealed));
+        }
+        self.validation.as_ref().map_or(Ok(()), |validation| validation.check(value, concealed))
+    }
+}
+
+fn invalid_number(value: &str, concealed: bool) -> String {
+    if concealed { t!("error_secret_invalid_number") } else { t_params!("error_invalid_number", &[value]) }
+}
+
+/// Order in which arguments are resolved: file order, except that an argument's dependencies,
+/// declared or used by its templates, are pulled in just before it. Fails on a declared dependency
+/// that isn't one of `arguments` and on a dependency cycle.
+pub fn resolution_order<'a>(
+    arguments: &'a [WorkflowArgument],
+    inspector: &dyn TemplateInspector
+) -> Result<Vec<&'a WorkflowArgument>, ValidationError> {
+    let position: HashMap<&str, usize> =
+        arguments.iter().enumerate().map(|(index, arg)| (arg.name.as_str(), index)).collect();
+
+    let mut dependencies = Vec::with_capacity(arguments.len());
+    for arg in arguments {
+        let indices = arg
+            .dependencies()
+            .map(|dependency| {
+                position
+                    .get(dependency)
+                    .copied()
+                    .ok_or_else(|| ValidationError::UnknownDependency(arg.name.clone(), dependency.to_string()))
+            })
+            .collect::<Result<Vec<usize>, _>>()?;
+        let mut references: Vec<usize> =
+            arg.template_references(inspector).iter().filter_map(|name| position.get(name.as_str()).copied()).collect();
+        references.sort_unstable();
+        dependencies.push(indices.into_iter().chain(references).collect());
+    }
+
+    let mut done = vec![false; arguments.len()];
+    let mut path = Vec::new();
+    let mut order = Vec::with_capacity(arguments.len());
+    for index in 0..arguments.len() {
+        visit(index, arguments, &dependencies, &mut done, &mut path, &mut order)?;
+    }
+
+    Ok(order)
+}
+
+/// Depth-first step of [`resolution_order`]: place the dependencies of `index`, then `index`.
+/// `path` holds the arguments being visited, so meeting one of them again closes a cycle.
+fn visit<'a>(
+    index: usize,
+    arguments: &'a [WorkflowArgument],
+    dependencies: &[Vec<usize>],
+    done: &mut [bool],
+    path: &mut Vec<usize>,
+    order: &mut Vec<&'a WorkflowArgument>
+) -> Result<(), ValidationError> {
+    if done[index] {
+        return Ok(());
+    }
+    if let Some(start) = path.iter().position(|&i| i == index) {
+        let cycle: Vec<&str> = path[start..].iter().chain([&index]).map(|&i| arguments[i].name.as_str()).collect();
+        return Err(ValidationError::DependencyCycle(cycle.join(" -> ")));
+    }
+
+    path.push(index);
+    for &dependency in &dependencies[index] {
+        visit(dependency, arguments, dependencies, done, path, order)?;
+    }
+    path.pop();
+
+    done[index] = true;
+    order.push(&arguments[index]);
+    Ok(())
+}
+
+/// Declarative rules for an argument value
+///
+/// ```yaml
+/// validation:
+///   not_empty: true
+///   pattern: "^[a-z0-9-]+$"   # regex the whole value must match
+///   min_length: 3
+///   max_length: 63
+///   min: 1                     # numeric bounds, for Number arguments
+///   max: 65535
+///   integer: true
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ArgumentValidation {
+    /// Regular expression the whole value must match
+    pub pattern:         Option<String>,
+    /// Minimum length in characters
+    pub min_length:      Option<usize>,
+    /// Maximum length in characters
+    pub max_length:      Option<usize>,
+    /// Smallest accepted number
+    pub min:             Option<f64>,
+    /// Largest accepted number
+    pub max:             Option<f64>,
+    /// Only accept whole numbers
+    #[serde(default)]
+    pub integer:         bool,
+    /// Reject empty or whitespace-only values
+    #[serde(default)]
+    pub not_empty:       bool,
+    /// `pattern` compiled on first use, so prompts don't recompile it on every keystroke
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub(crate) compiled: OnceLock<Result<Regex, String>>
+}
+
+impl ArgumentValidation {
+    /// `pattern` anchored to the whole value and compiled, or the localized reason it can't be
+    pub fn regex(&self) -> Result<Option<&Regex>, String> {
+        let Some(pattern) = &self.pattern else { return Ok(None) };
+        self.compiled
+            .get_or_init(|| {
+                Regex::new(&format!("^(?:{})$", pattern))
+                    .map_err(|e| t_params!("error_invalid_validation_pattern", &[pattern, &e.to_string()]))
+            })
+            .as_ref()
+            .map(Some)
+            .map_err(Clone::clone)
+    }
+
+    /// Check `value` against every rule, returning the localized message of the first one it
+    /// breaks. A `concealed` value (a secret) is never quoted back in the message.
+    pub fn check(&self, value: &str, concealed: bool) -> Result<(), String> {
+        if self.not_empty && value.trim().is_empty() {
+            return Err(t!("error_value_empty"));
+        }
+
+        let length = value.chars().count();
+        if let Some(min_length) = self.min_length.filter(|min| length < *min) {
+            return Err(t_params!("error_value_too_short", &[&min_length.to_string()]));
+        }
+        if let Some(max_length) = self.max_length.filter(|max| length > *max) {
+            return Err(t_params!("error_value_too_long", &[&max_length.to_string()]));
+        }
+
+        if let Some(regex) = self.regex()?
+            && !regex.is_match(value)
+        {
+            let pattern = self.pattern.as_deref().unwrap_or_default();
+            return Err(if concealed {
+                t_params!("error_secret_pattern_mismatch", &[pattern])
+            } else {
+                t_params!("error_value_pattern_mismatch", &[value, pattern])
+            });
+        }
+
+        if self.integer || self.min.is_some() || self.max.is_some() {
+            let number = value.trim().parse::<f64>().map_err(|_| invalid_number(value, concealed))?;
+            if self.integer && number.fract() != 0.0 {
+                return Err(if concealed {
+                    t!("error_secret_not_integer")
+                } else {
+                    t_params!("error_value_not_integer", &[value])
+                });
+            }
+            if let Some(min) = self.min.filter(|min| number < *min) {
+                return Err(t_params!("error_value_below_min", &[&min.to_string()]));
+            }
+            if let Some(max) = self.max.filter(|max| number > *max) {
+                return Err(t_params!("error_value_above_max", &[&max.to_string()]));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Options for `Path` arguments
+///
+/// ```yaml
+/// path:
+///   must_exist: true
+///   kind: file             # file or dir
+///   glob: "*.yaml"         # file names offered and accepted
+///   relative_to: workflow  # cwd (default) or workflow
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PathOptions {
+    /// Reject paths that don't exist
+    #[serde(default)]
+    pub must_exist:  bool,
+    /// Only accept files or only directories
+    pub kind:        Option<PathKind>,
+    /// Pattern file names must match
+    pub glob:        Option<String>,
+    /// Directory relative paths start from
+    #[serde(default)]
+    pub relative_to: PathBase
+}
+
+impl PathOptions {
+    /// Whether the file name of `path` matches `glob`; always true without one
+    pub fn matches_glob(&self, path: &Path) -> Result<bool, String> {
+        let Some(glob) = &self.glob else {
+            return Ok(true);
+        };
+        let matcher = globset::Glob::new(glob)
+            .map_err(|e| t_params!("error_invalid_path_glob", &[glob, &e.to_string()]))?
+            .compile_matcher();
+        Ok(path.file_name().is_some_and(|name| matcher.is_match(name)))
+    }
+}
+
+/// Kind of filesystem entry a `Path` argument accepts
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PathKind {
+    File,
+    Dir
+}
+
+/// Directory a relative `Path` value is taken from
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PathBase {
+    /// The directory `wf` runs in
+    #[default]
+    Cwd,
+    /// The directory holding the workflow file; values are rendered as absolute paths
+    Workflow
 }
 
 /// Returns the default argument type when not specified in YAML.
@@ -121,7 +618,10 @@ fn default_arg_type() -> ArgumentType {
 /// - `Enum`: Selection from dynamically generated options
 /// - `Number`: Numeric input (validated as number)
 /// - `Boolean`: True/false selection
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// - `Secret`: Masked text input, never written to the event store
+/// - `Path`: File or directory picked from the filesystem or typed in
+/// - `Computed`: Rendered from other arguments, never prompted
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "PascalCase")]
 pub enum ArgumentType {
     /// Free text input with optional default value
@@ -131,11 +631,32 @@ pub enum ArgumentType {
     /// Numeric input with validation
     Number,
     /// Boolean true/false selection
-    Boolean
+    Boolean,
+    /// Text typed without echo; stored redacted in events
+    Secret,
+    /// File or directory path, checked against its `path` options
+    Path,
+    /// Value rendered from its `expression`
+    Computed
+}
+
+/// How an argument value is quoted when it is rendered into the command
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteStyle {
+    /// Quote only when the value contains characters the shell would interpret
+    Auto,
+    /// Insert the value verbatim
+    #[serde(rename = "none")]
+    Raw,
+    /// Always wrap in single quotes
+    Single,
+    /// Always wrap in double quotes, escaping what the shell expands inside them
+    Double
 }
 
 /// Command execution context
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WorkflowContext {
     /// Unique session identifier
     pub session_id: String,
@@ -191,6 +712,53 @@ impl Default for WorkflowContext {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapter::command::template::TeraInspector;
+
+    #[test]
+    fn slugify_collapses_separators() {
+        assert_eq!(slugify("Deploy to K8s"), "deploy-to-k8s");
+        assert_eq!(slugify("  git: undo last commit!! "), "git-undo-last-commit");
+        assert_eq!(slugify("already-a-slug"), "already-a-slug");
+        assert_eq!(slugify(""), "");
+    }
+
+    #[test]
+    fn supports_shell_treats_empty_list_as_any() {
+        let mut wf: Workflow =
+            serde_yaml::from_str("name: t\ncommand: echo\ndescription: d\narguments: []\ntags: []\nshells: []")
+                .unwrap();
+        assert!(wf.supports_shell(Shell::Fish));
+
+        wf.shells = vec!["Bash".to_string(), "zsh".to_string()];
+        assert!(wf.supports_shell(Shell::Bash));
+        assert!(!wf.supports_shell(Shell::Fish));
+    }
+
+    #[test]
+    fn text_arguments_quote_automatically_by_default() {
+        let text: WorkflowArgument = serde_yaml::from_str("name: a\ndescription: d").unwrap();
+        assert_eq!(text.quote_style(), QuoteStyle::Auto);
+
+        let number: WorkflowArgument = serde_yaml::from_str("name: a\narg_type: Number\ndescription: d").unwrap();
+        assert_eq!(number.quote_style(), QuoteStyle::Raw);
+
+        let raw: WorkflowArgument = serde_yaml::from_str("name: a\ndescription: d\nquote: none").unwrap();
+        assert_eq!(raw.quote_style(), QuoteStyle::Raw);
+    }
+
+    #[test]
+    fn dialect_follows_first_shell() {
+        let mut wf: Workflow =
+            serde_yaml::from_str("name: t\ncommand: echo\ndescription: d\narguments: []\ntags: []\nshells: []")
+                .unwrap();
+        assert_eq!(wf.dialect(), ShellDialect::Posix);
+
+        wf.shells = vec!["fish".to_string(), "bash".to_string()];
+        assert_eq!(wf.dialect(), ShellDialect::Fish);
+
+        wf.shells = vec!["pwsh".to_string()];
+        assert_eq!(wf.dialect(), ShellDialect::PowerShell);
+    }
 
     #[test]
     fn default_arg_type_is_text() {
@@ -366,6 +934,111 @@ mod tests {
         assert_eq!(wf.author_url.as_deref(), Some("https://alice.dev"));
     }
 
+    #[test]
+    fn validation_rules_report_the_first_broken_rule() {
+        let rules = ArgumentValidation {
+            pattern: Some("^[a-z-]+$".to_string()),
+            min_length: Some(3),
+            max_length: Some(8),
+            not_empty: true,
+            ..Default::default()
+        };
+        assert!(rules.check("api-gw", false).is_ok());
+        assert_eq!(rules.check("  ", false).unwrap_err(), t!("error_value_empty"));
+        assert_eq!(rules.check("ab", false).unwrap_err(), t_params!("error_value_too_short", &["3"]));
+        assert_eq!(rules.check("api-gateway", false).unwrap_err(), t_params!("error_value_too_long", &["8"]));
+        assert_eq!(
+            rules.check("API", false).unwrap_err(),
+            t_params!("error_value_pattern_mismatch", &["API", "^[a-z-]+$"])
+        );
+    }
+
+    #[test]
+    fn numeric_rules_check_bounds_and_integers() {
+        let rules = ArgumentValidation { min: Some(1.0), max: Some(10.0), integer: true, ..Default::default() };
+        assert!(rules.check("10", false).is_ok());
+        assert_eq!(rules.check("0", false).unwrap_err(), t_params!("error_value_below_min", &["1"]));
+        assert_eq!(rules.check("11", false).unwrap_err(), t_params!("error_value_above_max", &["10"]));
+        assert_eq!(rules.check("2.5", false).unwrap_err(), t_params!("error_value_not_integer", &["2.5"]));
+        assert_eq!(rules.check("x", false).unwrap_err(), t_params!("error_invalid_number", &["x"]));
+    }
+
+    #[test]
+    fn secret_values_never_appear_in_validation_errors() {
+        crate::i18n::display::TextManager::init(None, crate::i18n::Language::English);
+        let secret = "hunter2-token";
+        let yaml = r#"
+            name: token
+            arg_type: Secret
+            description: "API token"
+            validation:
+              pattern: "[a-z]{4}"
+        "#;
+        let arg: WorkflowArgument = serde_yaml::from_str(yaml).unwrap();
+        let err = arg.check_value(secret).unwrap_err();
+        assert_eq!(err, t_params!("error_secret_pattern_mismatch", &["[a-z]{4}"]));
+        assert!(!err.contains(secret));
+
+        let arg =
+            WorkflowArgument { validation: Some(ArgumentValidation { integer: true, ..Default::default() }), ..arg };
+        assert!(!arg.check_value(secret).unwrap_err().contains(secret));
+        assert!(!arg.check_value("2.5").unwrap_err().contains("2.5"));
+    }
+
+    #[test]
+    fn invalid_pattern_is_reported() {
+        let rules = ArgumentValidation { pattern: Some("([".to_string()), ..Default::default() };
+        let err = rules.check("anything", false).unwrap_err();
+        assert_ne!(err, t_params!("error_value_pattern_mismatch", &["anything", "(["]));
+    }
+
+    #[test]
+    fn pattern_must_match_the_whole_value() {
+        let rules = ArgumentValidation { pattern: Some("[a-z]+|[0-9]+".to_string()), ..Default::default() };
+        assert!(rules.check("api", false).is_ok());
+        assert!(rules.check("8080", false).is_ok());
+        assert!(rules.check("api-8080", false).is_err());
+        assert!(rules.check("x; rm -rf /", false).is_err());
+    }
+
+    #[test]
+    fn validation_parses_from_yaml() {
+        let yaml = r#"
+            name: port
+            arg_type: Number
+            description: "Port"
+            validation:
+              min: 1
+              max: 65535
+              integer: true
+        "#;
+        let arg: WorkflowArgument = serde_yaml::from_str(yaml).unwrap();
+        let rules = arg.validation.as_ref().unwrap();
+        assert_eq!((rules.min, rules.max, rules.integer, rules.not_empty), (Some(1.0), Some(65535.0), true, false));
+        assert!(arg.check_value("8080").is_ok());
+        assert!(arg.check_value("http").is_err());
+    }
+
+    #[test]
+    fn workflow_with_steps_needs_no_command() {
+        let yaml = r#"
+            name: "Probe"
+            description: "Forward, probe, clean up"
+            arguments: []
+            tags: []
+            shells: []
+            steps:
+              - name: "Forward"
+                command: "kubectl port-forward svc/api 8080:80 &"
+              - name: "Probe"
+                command: "curl -s localhost:8080/health"
+        "#;
+        let wf: Workflow = serde_yaml::from_str(yaml).unwrap();
+        assert!(wf.is_multi_step());
+        assert!(wf.command.is_empty());
+        assert_eq!(wf.steps[1].name, "Probe");
+    }
+
     #[test]
     fn workflow_display_shows_name() {
         let wf = Workflow {
@@ -377,7 +1050,11 @@ mod tests {
             source_url:  None,
             author:      None,
             author_url:  None,
-            shells:      vec![]
+            execute:     false,
+            steps:       vec![],
+            shells:      vec![],
+            namespace:   None,
+            origin:      None
         };
         assert_eq!(format!("{}", wf), "My Workflow");
     }
@@ -414,6 +1091,92 @@ mod tests {
         assert_eq!(arg.dynamic_resolution.as_deref(), Some("namespace"));
     }
 
+    fn graph(yaml: &str) -> Vec<WorkflowArgument> {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn names(order: Vec<&WorkflowArgument>) -> Vec<&str> {
+        order.into_iter().map(|arg| arg.name.as_str()).collect()
+    }
+
+    #[test]
+    fn resolution_order_puts_dependencies_first_and_keeps_file_order() {
+        let args = graph(
+            r#"
+- { name: pod, description: "", depends_on: [cluster, namespace] }
+- { name: cluster, description: "" }
+- { name: port, description: "" }
+- { name: namespace, description: "", dynamic_resolution: cluster }
+"#
+        );
+        assert_eq!(
+            names(resolution_order(&args, &TeraInspector).unwrap()),
+            vec!["cluster", "namespace", "pod", "port"]
+        );
+    }
+
+    #[test]
+    fn resolution_order_follows_the_arguments_templates_use() {
+        let args = graph(
+            r#"
+- { name: release, description: "", arg_type: Computed, expression: "{{ app }}-{{ ctx.user }}" }
+- { name: pod, description: "", arg_type: Enum, enum_command: "kubectl get pods -n {{ namespace }}" }
+- { name: namespace, description: "" }
+- { name: app, description: "" }
+"#
+        );
+        assert_eq!(names(resolution_order(&args, &TeraInspector).unwrap()), vec!["app", "release", "namespace", "pod"]);
+
+        let args = graph(
+            r#"
+- { name: a, description: "", arg_type: Computed, expression: "{{ b }}" }
+- { name: b, description: "", arg_type: Computed, expression: "{{ a }}" }
+"#
+        );
+        let order = resolution_order(&args, &TeraInspector);
+        assert!(matches!(order, Err(ValidationError::DependencyCycle(cycle)) if cycle == "a -> b -> a"));
+    }
+
+    #[test]
+    fn resolution_order_resolves_what_a_condition_checks_first() {
+        let args = graph(
+            r#"
+- { name: replicas, description: "", arg_type: Number, when: 'action == "scale"' }
+- { name: action, description: "" }
+"#
+        );
+        assert_eq!(names(resolution_order(&args, &TeraInspector).unwrap()), vec!["action", "replicas"]);
+    }
+
+    #[test]
+    fn resolution_order_rejects_unknown_dependencies() {
+        let args = graph(r#"[{ name: pod, description: "", depends_on: [namespce] }]"#);
+        assert!(matches!(
+            resolution_order(&args, &TeraInspector),
+            Err(ValidationError::UnknownDependency(name, dependency)) if name == "pod" && dependency == "namespce"
+        ));
+    }
+
+    #[test]
+    fn resolution_order_names_the_cycle() {
+        let args = graph(
+            r#"
+- { name: region, description: "" }
+- { name: a, description: "", depends_on: [region, c] }
+- { name: b, description: "", depends_on: [a] }
+- { name: c, description: "", depends_on: [b] }
+"#
+        );
+        let Err(ValidationError::DependencyCycle(cycle)) = resolution_order(&args, &TeraInspector) else {
+            panic!("expected a cycle")
+        };
+        assert_eq!(cycle, "a -> c -> b -> a");
+
+        let args = graph(r#"[{ name: a, description: "", depends_on: [a] }]"#);
+        let order = resolution_order(&args, &TeraInspector);
+        assert!(matches!(order, Err(ValidationError::DependencyCycle(cycle)) if cycle == "a -> a"));
+    }
+
     #[test]
     fn argument_default_value_variants() {
         let yaml = "name: x\ndescription: d\ndefault_value: hello";
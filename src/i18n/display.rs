@@ -1,9 +1,4 @@
-use std::{
-    collections::HashMap,
-    env,
-    path::{Path, PathBuf},
-    sync::OnceLock
-};
+use std::{collections::HashMap, env, path::PathBuf, sync::OnceLock};
 
 use crate::i18n::{
     Language,
@@ -21,37 +16,20 @@ pub struct TextManager {
 static TEXT_MANAGER: OnceLock<TextManager> = OnceLock::new();
 
 impl TextManager {
-    /// Initialize the text manager with a config directory
-    pub fn init(config_dir: Option<PathBuf>) -> &'static Self {
+    /// Initialize the text manager with a config directory and the language to speak
+    pub fn init(config_dir: Option<PathBuf>, language: Language) -> &'static Self {
         TEXT_MANAGER.get_or_init(|| {
             let location = config_dir.unwrap_or_else(|| env::current_dir().unwrap_or_default());
-            let loader = LanguageLoader::new(location.clone());
+            let loader = LanguageLoader::new(location);
 
             let mut cache = HashMap::new();
             cache.insert(Language::English, loader.load(Language::English));
             cache.insert(Language::Spanish, loader.load(Language::Spanish));
 
-            let current_language = Self::read_current_language_from_config(&location).unwrap_or(Language::English);
-
-            Self { current_language, cache }
+            Self { current_language: language, cache }
         })
     }
 
-    /// Read the current language from the config file
-    fn read_current_language_from_config(config_dir: &Path) -> Option<Language> {
-        let lang_file = config_dir.join("language.txt");
-        if lang_file.exists() {
-            if let Ok(content) = std::fs::read_to_string(&lang_file) {
-                let lang_code = content.trim();
-                Language::try_from(lang_code).ok()
-            } else {
-                None
-            }
-        } else {
-            None
-        }
-    }
-
     /// Get text for a key in the current language
     pub fn get(&self, key: &str) -> String {
         self.cache
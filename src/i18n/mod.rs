@@ -5,9 +5,11 @@ pub mod loader;
 pub mod macros;
 
 /// Supported languages
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Language {
+    #[serde(rename = "en")]
     English,
+    #[serde(rename = "es")]
     Spanish /* French,
              * German,
              * Chinese, */
@@ -9,17 +9,35 @@ use std::sync::Arc;
 
 use crate::{
     adapter::{
-        executor::ShellExecutor, filesystem::StdFileSystem, git::Git2Client, output::CliOutput, prompt::CliPrompt,
+        executor::ShellExecutor,
+        filesystem::StdFileSystem,
+        git::Git2Client,
+        output::CliOutput,
+        prompt::CliPrompt,
+        secret::SecretStore,
+        sink::{ClipboardSink, StdoutSink},
         storage::EventStoreFactory
     },
     domain::error::WorkflowError,
-    i18n::display::TextManager,
+    i18n::{Language, display::TextManager},
     port::{
-        executor::CommandExecutor, filesystem::FileSystem, git::GitClient, output::OutputWriter, prompt::UserPrompt
+        executor::CommandExecutor, filesystem::FileSystem, git::GitClient, output::OutputWriter, prompt::UserPrompt,
+        sink::ResultSink
     },
     service::config::AppConfig
 };
 
+/// Options picked on the command line that change how the AppContext is wired
+#[derive(Debug, Clone, Default)]
+pub struct AppOptions {
+    /// Write only the rendered command to stdout; prompts and messages go to stderr
+    pub print:               bool,
+    /// Reject workflow files with fields the format doesn't know
+    pub deny_unknown_fields: bool,
+    /// Profile to run with; `WF_PROFILE` when not given
+    pub profile:             Option<String>
+}
+
 /// Application context for dependency injection
 #[derive(Clone)]
 pub struct AppContext {
@@ -38,30 +56,48 @@ pub struct AppContext {
     /// File system operations
     pub filesystem:   Arc<dyn FileSystem>,
     /// Output writer for CLI display
-    pub output:       Arc<dyn OutputWriter>
+    pub output:       Arc<dyn OutputWriter>,
+    /// Where the rendered command ends up (clipboard or stdout)
+    pub sink:         Arc<dyn ResultSink>,
+    /// Secret argument values, kept out of the event store
+    pub secrets:      Arc<SecretStore>
 }
 
 impl AppContext {
     /// Creates a new app context with configuration from file (or defaults)
     ///
     /// Initializes the application with:
-    /// - Configuration from file or defaults
+    /// - Configuration from `config.toml` (or defaults), the active profile and `WF_*` overrides
     /// - Text manager for i18n
     /// - Git client for repository operations
     /// - Event store with shared RocksDB instance for Journal/EventStore coordination
-    pub fn init() -> Result<Self, WorkflowError> {
-        let temp_config = AppConfig::init()?;
-        let storage_type = temp_config.get_current_storage()?;
-
-        let config = AppConfig::with_storage_type(storage_type)?;
-        config.ensure_dirs_exist()?;
-        let text_manager = TextManager::init(Some(config.config_dir.clone()));
-        let output = Arc::new(CliOutput::default()) as Arc<dyn OutputWriter>;
+    /// - Output and result sink chosen by `options` (clipboard, or stdout in print mode)
+    /// - An empty in-memory secret store
+    pub fn init(options: &AppOptions) -> Result<Self, WorkflowError> {
+        let mut config = match AppConfig::init(options.profile.as_deref()) {
+            Ok(config) => config,
+            Err(_) => {
+                // Messages are translated as they're built, and the configured language isn't
+                // known here, so build the error again in English
+                TextManager::init(None, Language::English);
+                AppConfig::init(options.profile.as_deref())?
+            }
+        };
+        config.deny_unknown_fields = options.deny_unknown_fields;
+        let text_manager = TextManager::init(Some(config.config_dir.clone()), config.settings.ui.language);
+        let output = if options.print { CliOutput::stderr_only() } else { CliOutput::default() };
+        let output = Arc::new(output) as Arc<dyn OutputWriter>;
         let git_client = Arc::new(Git2Client::new(output.clone())) as Arc<dyn GitClient>;
         let event_store = EventStoreFactory::create(config.storage_type, Some(&config.database_path))?;
         let prompt = Arc::new(CliPrompt::new()) as Arc<dyn UserPrompt>;
         let executor = Arc::new(ShellExecutor::new()) as Arc<dyn CommandExecutor>;
         let filesystem = Arc::new(StdFileSystem::new()) as Arc<dyn FileSystem>;
+        let sink = if options.print {
+            Arc::new(StdoutSink) as Arc<dyn ResultSink>
+        } else {
+            Arc::new(ClipboardSink::new(prompt.clone(), output.clone(), config.settings.clipboard.confirm))
+                as Arc<dyn ResultSink>
+        };
 
         Ok(Self {
             config,
@@ -71,7 +107,9 @@ impl AppContext {
             prompt,
             executor,
             filesystem,
-            output
+            output,
+            sink,
+            secrets: Arc::new(SecretStore::new())
         })
     }
 }
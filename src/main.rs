@@ -19,14 +19,44 @@
 //! # Interactive selection from available workflows
 //! workflow
 //!
-//! # Execute a specific workflow
-//! workflow "my-workflow.yaml"
+//! # Run a discovered workflow by name (exact, slug or fuzzy match)
+//! workflow run deploy
+//!
+//! # Execute a workflow straight from a YAML file
+//! workflow file ./ops/deploy.yaml
+//!
+//! # Run the resolved command right away and record its exit code
+//! workflow run deploy --exec
+//!
+//! # Print the resolved command instead of copying it (for `$(...)` and `eval`)
+//! workflow --print
+//!
+//! # Bind Ctrl-G to a widget that drops the resolved command onto the prompt line
+//! eval "$(workflow shell-init zsh)"
 //!
 //! # List all available workflows
 //! workflow --list
 //!
-//! # Sync workflows from remote repository
-//! workflow sync --ssh-key ~/.ssh/id_rsa --remote-url git@github.com:user/workflows.git
+//! # JSON Schema for editor completion and validation of workflow files
+//! workflow schema > workflow.schema.json
+//!
+//! # Check workflow files for mistakes (non-zero exit on errors, JSON for CI)
+//! workflow lint
+//! workflow lint ./workflows --json --strict
+//!
+//! # Sync workflows from named remotes, each into its own folder and namespace
+//! workflow remote add team git@github.com:user/workflows.git --ssh-key ~/.ssh/id_rsa
+//! workflow remote list
+//! workflow sync          # all remotes
+//! workflow sync team     # just one
+//!
+//! # Run with another profile from config.toml (or set WF_PROFILE=acme)
+//! workflow --profile acme
+//!
+//! # Settings (config.toml); any key can be overridden with WF_*, e.g. WF_UI_PAGE_SIZE=20
+//! workflow config list
+//! workflow config set ui.page_size 20
+//! workflow config get storage.backend
 //!
 //! # Language management
 //! workflow lang set en
@@ -50,14 +80,19 @@ use ractor::{
     rpc::{CallResult, call}
 };
 use workflow::{
+    AppOptions,
     actor::{Guardian, GuardianMessage},
     domain::{
         command::{
-            CompleteWorkflowCommand, DeleteAggregateCommand, DiscoverWorkflowsCommand, GetCurrentLanguageCommand,
-            GetCurrentStorageCommand, InteractivelySelectWorkflowCommand, LangCommands, ListAggregatesCommand,
-            ListLanguagesCommand, ListWorkflowsCommand, PurgeStorageCommand, ReplayAggregateCommand,
-            ResolveArgumentsCommand, SetLanguageCommand, SetStorageCommand, StartWorkflowCommand, StorageCommands,
-            SyncWorkflowsCommand, WorkflowCli, WorkflowCliCommand, WorkflowCommand
+            AddRemoteCommand, CompleteWorkflowCommand, ConfigCommands, DeleteAggregateCommand,
+            DiscoverWorkflowsCommand, ExecuteWorkflowCommand, GetConfigCommand, GetCurrentLanguageCommand,
+            GetCurrentStorageCommand, InteractivelySelectWorkflowCommand, LangCommands, LintWorkflowsCommand,
+            ListAggregatesCommand, ListConfigCommand, ListLanguagesCommand, ListRemotesCommand, ListWorkflowsCommand,
+            LoadWorkflowFromFileCommand, PrintSchemaCommand, PurgeStorageCommand, RemoteCommands, RemoveRemoteCommand,
+            ReplayAggregateCommand, ResolveArgumentsCommand, RunWorkflowStepsCommand, SelectWorkflowByNameCommand,
+            SetConfigCommand, SetLanguageCommand, SetStorageCommand, ShellInitCommand, StartWorkflowCommand,
+            StorageCommands, SyncWorkflowsCommand, UnsetConfigCommand, WorkflowCli, WorkflowCliCommand,
+            WorkflowCommand
         },
         error::WorkflowError,
         workflow::WorkflowContext
@@ -67,24 +102,53 @@ use workflow::{
 
 #[tokio::main]
 async fn main() -> Result<(), WorkflowError> {
-    ctrlc::set_handler(|| std::process::exit(0)).ok();
-
-    let guardian_ref = Guardian::spawn_system()
-        .await
-        .map_err(|e| WorkflowError::Execution(t_params!("error_failed_to_start_actor_system", &[&e.to_string()])))?;
+    // A command running with --exec gets the interrupt and wf records how it ended; anywhere else
+    // wf stops right away, as it always has
+    ctrlc::set_handler(|| {
+        if !workflow::adapter::executor::interrupt_attached() {
+            std::process::exit(0);
+        }
+    })
+    .ok();
 
     let cli = WorkflowCli::parse();
     let context = WorkflowContext::new();
 
+    let guardian_ref = Guardian::spawn_system(AppOptions {
+        print:               cli.print,
+        deny_unknown_fields: cli.deny_unknown_fields,
+        profile:             cli.profile.clone()
+    })
+    .await
+    .map_err(|e| WorkflowError::Execution(t_params!("error_failed_to_start_actor_system", &[&e.to_string()])))?;
+
+    let resolve = ResolveArgumentsCommand { arguments: cli.args, no_prompt: cli.no_prompt, execute: cli.exec };
+
     let result = match cli.command {
-        Some(WorkflowCliCommand::Sync { ssh_key, remote_url, branch }) => {
+        Some(WorkflowCliCommand::Sync { name }) => {
             submit_command_to_actor_system(
                 &guardian_ref,
-                SyncWorkflowsCommand { ssh_key, remote_url, branch }.into(),
+                SyncWorkflowsCommand { remote: name, pending: vec![], failed: vec![] }.into(),
                 context
             )
             .await
         }
+        Some(WorkflowCliCommand::Remote { command }) => match command {
+            RemoteCommands::Add { name, url, branch, ssh_key, path } => {
+                submit_command_to_actor_system(
+                    &guardian_ref,
+                    AddRemoteCommand { name, url, branch, ssh_key, path }.into(),
+                    context
+                )
+                .await
+            }
+            RemoteCommands::List => {
+                submit_command_to_actor_system(&guardian_ref, ListRemotesCommand.into(), context).await
+            }
+            RemoteCommands::Remove { name } => {
+                submit_command_to_actor_system(&guardian_ref, RemoveRemoteCommand { name }.into(), context).await
+            }
+        },
         Some(WorkflowCliCommand::Lang { command }) => match command {
             LangCommands::Set { language } => {
                 submit_command_to_actor_system(&guardian_ref, SetLanguageCommand { language }.into(), context).await
@@ -119,22 +183,62 @@ async fn main() -> Result<(), WorkflowError> {
                 submit_command_to_actor_system(&guardian_ref, PurgeStorageCommand.into(), context).await
             }
         },
+        Some(WorkflowCliCommand::Config { command }) => match command {
+            ConfigCommands::Get { key } => {
+                submit_command_to_actor_system(&guardian_ref, GetConfigCommand { key }.into(), context).await
+            }
+            ConfigCommands::Set { key, value } => {
+                submit_command_to_actor_system(&guardian_ref, SetConfigCommand { key, value }.into(), context).await
+            }
+            ConfigCommands::Unset { key } => {
+                submit_command_to_actor_system(&guardian_ref, UnsetConfigCommand { key }.into(), context).await
+            }
+            ConfigCommands::List => {
+                submit_command_to_actor_system(&guardian_ref, ListConfigCommand.into(), context).await
+            }
+        },
         Some(WorkflowCliCommand::List) => {
             submit_command_to_actor_system(&guardian_ref, DiscoverWorkflowsCommand.into(), context.clone()).await?;
             submit_command_to_actor_system(&guardian_ref, ListWorkflowsCommand.into(), context).await
         }
-        Some(WorkflowCliCommand::File { .. }) => {
-            Err(WorkflowError::Other(t!("error_file_workflow_execution_not_yet_implemented_in_actor_system")))
+        Some(WorkflowCliCommand::Run { name }) => {
+            submit_command_to_actor_system(&guardian_ref, DiscoverWorkflowsCommand.into(), context.clone()).await?;
+            submit_command_to_actor_system(
+                &guardian_ref,
+                SelectWorkflowByNameCommand { name, no_prompt: resolve.no_prompt }.into(),
+                context.clone()
+            )
+            .await?;
+            run_selected_workflow(&guardian_ref, resolve, context).await
+        }
+        Some(WorkflowCliCommand::File { file }) => {
+            submit_command_to_actor_system(
+                &guardian_ref,
+                LoadWorkflowFromFileCommand { path: file }.into(),
+                context.clone()
+            )
+            .await?;
+            run_selected_workflow(&guardian_ref, resolve, context).await
+        }
+        Some(WorkflowCliCommand::Lint { paths, json, strict }) => {
+            submit_command_to_actor_system(&guardian_ref, LintWorkflowsCommand { paths, json, strict }.into(), context)
+                .await
+        }
+        Some(WorkflowCliCommand::Schema) => {
+            submit_command_to_actor_system(&guardian_ref, PrintSchemaCommand.into(), context).await
+        }
+        Some(WorkflowCliCommand::ShellInit { shell, key }) => {
+            submit_command_to_actor_system(&guardian_ref, ShellInitCommand { shell, key }.into(), context).await
         }
         None => {
             submit_command_to_actor_system(&guardian_ref, DiscoverWorkflowsCommand.into(), context.clone()).await?;
-            submit_command_to_actor_system(&guardian_ref, InteractivelySelectWorkflowCommand.into(), context.clone())
-                .await?;
-            submit_command_to_actor_system(&guardian_ref, StartWorkflowCommand.into(), context.clone()).await?;
-            submit_command_to_actor_system(&guardian_ref, ResolveArgumentsCommand.into(), context.clone()).await?;
-            submit_command_to_actor_system(&guardian_ref, CompleteWorkflowCommand.into(), context.clone()).await?;
-
-            Ok(())
+            submit_command_to_actor_system(
+                &guardian_ref,
+                InteractivelySelectWorkflowCommand { shell: cli.shell }.into(),
+                context.clone()
+            )
+            .await?;
+            run_selected_workflow(&guardian_ref, resolve, context).await
         }
     };
 
@@ -147,16 +251,34 @@ async fn main() -> Result<(), WorkflowError> {
     result
 }
 
+/// Drive a selected workflow through start, argument resolution, execution (when asked for) or its
+/// steps, and completion
+async fn run_selected_workflow(
+    guardian_ref: &ActorRef<GuardianMessage>,
+    resolve: ResolveArgumentsCommand,
+    context: WorkflowContext
+) -> Result<(), WorkflowError> {
+    let execute = ExecuteWorkflowCommand { forced: resolve.execute };
+    let steps = RunWorkflowStepsCommand { forced: resolve.execute, no_prompt: resolve.no_prompt };
+
+    submit_command_to_actor_system(guardian_ref, StartWorkflowCommand.into(), context.clone()).await?;
+    submit_command_to_actor_system(guardian_ref, resolve.into(), context.clone()).await?;
+    submit_command_to_actor_system(guardian_ref, execute.into(), context.clone()).await?;
+    submit_command_to_actor_system(guardian_ref, steps.into(), context.clone()).await?;
+    submit_command_to_actor_system(guardian_ref, CompleteWorkflowCommand.into(), context).await
+}
+
 /// Submit a command to the actor system via the Guardian
 async fn submit_command_to_actor_system(
     guardian_ref: &ActorRef<GuardianMessage>,
     command: WorkflowCommand,
     context: WorkflowContext
 ) -> Result<(), WorkflowError> {
+    // The manager enforces `execution.timeout_secs` on the command itself and replies with the error
     match call(
         guardian_ref,
         |reply| GuardianMessage::SubmitCommand { command, context: Box::new(context), reply },
-        Some(std::time::Duration::from_secs(300))
+        None
     )
     .await
     {
@@ -96,6 +96,18 @@ pub trait Command: Debug + Send + Sync {
     fn is_mutating(&self) -> bool {
         true
     }
+
+    /// Check if this command runs the workflow's own commands, which take as long as they take:
+    /// `execution.timeout_secs` doesn't apply to it
+    fn runs_processes(&self) -> bool {
+        false
+    }
+
+    /// Check if the command should be processed again against the state its events led to,
+    /// e.g. for the next step of a run. Each pass is persisted before the next one starts.
+    fn continues(&self, _state: &WorkflowState) -> bool {
+        false
+    }
 }
 
 /// Trait for commands that can be undone
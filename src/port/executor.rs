@@ -7,4 +7,8 @@ use crate::domain::error::WorkflowError;
 pub trait CommandExecutor: Send + Sync {
     /// Execute a shell command and return its stdout on success
     async fn execute(&self, command: &str) -> Result<String, WorkflowError>;
+
+    /// Run a command attached to the terminal so its output streams live.
+    /// Returns the exit code, or `None` when the process was killed by a signal.
+    async fn run_attached(&self, command: &str) -> Result<Option<i32>, WorkflowError>;
 }
@@ -14,7 +14,9 @@ pub struct CloneOptions {
     /// SSH key path for authentication
     pub ssh_key: Option<String>,
     /// Branch to clone
-    pub branch:  Option<String>
+    pub branch:  Option<String>,
+    /// Folder of the repository to take files from, instead of its root
+    pub path:    Option<String>
 }
 
 /// Commit information
@@ -7,4 +7,6 @@ pub mod git;
 pub mod journal;
 pub mod output;
 pub mod prompt;
+pub mod sink;
 pub mod storage;
+pub mod template;
@@ -29,6 +29,9 @@ impl From<&str> for SelectOption {
     }
 }
 
+/// Check for a text input: `Err` carries the message shown to the user
+pub type TextValidator = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
 /// Port trait for user prompts (select, multi-select, text input, confirm)
 pub trait UserPrompt: Send + Sync {
     /// Present a single-select prompt and return the chosen option value
@@ -47,6 +50,18 @@ pub trait UserPrompt: Send + Sync {
     /// Present a text input prompt and return the entered value
     fn text(&self, prompt: &str, default: Option<&str>) -> Result<String, WorkflowError>;
 
+    /// Present a text input prompt that shows `validate`'s message inline and asks again
+    /// until the entered value is accepted
+    fn text_validated(
+        &self,
+        prompt: &str,
+        default: Option<&str>,
+        validate: TextValidator
+    ) -> Result<String, WorkflowError>;
+
+    /// Present a masked input prompt for a secret, asking again until `validate` accepts it
+    fn password(&self, prompt: &str, validate: TextValidator) -> Result<String, WorkflowError>;
+
     /// Present a yes/no confirmation prompt
     fn confirm(&self, prompt: &str, default: bool) -> Result<bool, WorkflowError>;
 }
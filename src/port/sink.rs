@@ -0,0 +1,8 @@
+use crate::domain::error::WorkflowError;
+
+/// Port trait for handing the rendered command over to the user
+/// (clipboard, stdout for shell integration, ...)
+pub trait ResultSink: Send + Sync {
+    /// Deliver the final command. `interactive` is false when prompting is disabled.
+    fn deliver(&self, command: &str, interactive: bool) -> Result<(), WorkflowError>;
+}
@@ -0,0 +1,16 @@
+//! Template inspection port trait
+//!
+//! This module lets the domain ask which variables a template uses without depending on the
+//! template engine.
+
+use std::collections::HashSet;
+
+/// Reads the variables templates use, without rendering them
+pub trait TemplateInspector {
+    /// Top-level variables `template` reads; loop and `set` variables are left out. Fails on a
+    /// syntax error, with the template engine's message.
+    fn variables(&self, template: &str) -> Result<HashSet<String>, String>;
+
+    /// Variables a `when:` condition uses
+    fn condition_variables(&self, condition: &str) -> Result<HashSet<String>, String>;
+}
@@ -1,4 +1,4 @@
-use std::{fs, path::PathBuf};
+use std::{collections::BTreeMap, fs, path::PathBuf};
 
 use directories::ProjectDirs;
 
@@ -6,6 +6,7 @@ use crate::{
     adapter::storage::EventStoreType,
     domain::error::{StorageError, WorkflowError},
     i18n::Language,
+    service::settings::Settings,
     t
 };
 
@@ -13,46 +14,82 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct AppConfig {
     /// Base configuration directory
-    pub config_dir:    PathBuf,
-    /// Workflows directory
-    pub workflows_dir: PathBuf,
+    pub config_dir:          PathBuf,
+    /// Global workflows directory, or the first one of the active profile
+    pub workflows_dir:       PathBuf,
+    /// More workflow directories (the profile's others, then `WF_PATH`), searched after the global
+    /// one
+    pub workflow_path:       Vec<PathBuf>,
     /// i18n directory
-    pub i18n_dir:      PathBuf,
+    pub i18n_dir:            PathBuf,
     /// Database file path (for RocksDB)
-    pub database_path: PathBuf,
+    pub database_path:       PathBuf,
     /// Journal path (for actor persistence)
-    pub journal_path:  PathBuf,
+    pub journal_path:        PathBuf,
     /// Storage backend type
-    pub storage_type:  EventStoreType
+    pub storage_type:        EventStoreType,
+    /// Reject workflow files with fields the format doesn't know
+    pub deny_unknown_fields: bool,
+    /// Settings from `config.toml` with the active profile and the `WF_*` overrides applied
+    pub settings:            Settings,
+    /// Name of the active profile, if any
+    pub profile:             Option<String>,
+    /// Argument defaults of the active profile, by argument name
+    pub argument_defaults:   BTreeMap<String, String>
 }
 
 // TODO: All the fs operations are all over the place, we should move them to the a trait
 // so they can be easily mocked for testing
-// TODO: The memory type should also come from the config
 impl AppConfig {
-    pub fn init() -> Result<Self, WorkflowError> {
-        let config = Self::new()?;
+    /// Load the configuration for `profile`, falling back to the one named by `WF_PROFILE`
+    pub fn init(profile: Option<&str>) -> Result<Self, WorkflowError> {
+        let profile =
+            profile.map(str::to_string).or_else(|| std::env::var("WF_PROFILE").ok()).filter(|p| !p.is_empty());
+        let config = Self::new(profile)?;
         config.ensure_dirs_exist()?;
         Ok(config)
     }
 
-    /// Creates a new app config with default storage backend
-    fn new() -> Result<Self, WorkflowError> {
-        Self::with_storage_type(EventStoreType::RocksDb)
-    }
-
-    /// Creates a new app config with specified storage backend
-    pub fn with_storage_type(storage_type: EventStoreType) -> Result<Self, WorkflowError> {
+    /// Creates a new app config from `config.toml`, the profile and the environment
+    fn new(profile: Option<String>) -> Result<Self, WorkflowError> {
         let project_dirs = ProjectDirs::from("org", "sagoez", "workflow")
             .ok_or(WorkflowError::from(StorageError::Io(t!("error_filesystem"))))?;
 
         let config_dir = project_dirs.config_dir().to_path_buf();
-        let workflows_dir = config_dir.join("workflows");
+        let mut workflows_dir = config_dir.join("workflows");
         let i18n_dir = config_dir.join("i18n");
-        let database_path = config_dir.join("rocksdb");
+        let mut database_path = config_dir.join("rocksdb");
+        let mut workflow_path: Vec<PathBuf> =
+            std::env::var_os("WF_PATH").map(|paths| std::env::split_paths(&paths).collect()).unwrap_or_default();
+        let mut settings = Settings::load(&config_dir)?;
+        let mut argument_defaults = BTreeMap::new();
+
+        if let Some(name) = &profile {
+            let active = settings.profile(name)?.clone();
+            let mut dirs = active.workflow_dirs(&config_dir, name);
+            workflows_dir = dirs.remove(0);
+            workflow_path.splice(0..0, dirs);
+            database_path = active.storage_path(&config_dir, name);
+            settings.remotes = active.remotes;
+            argument_defaults = active.defaults;
+        }
+
         let journal_path = database_path.clone();
+        let settings = settings.with_overrides(|name| std::env::var(name).ok())?;
 
-        Ok(Self { config_dir, workflows_dir, i18n_dir, database_path, journal_path, storage_type })
+        Ok(Self {
+            config_dir,
+            workflows_dir,
+            workflow_path,
+            i18n_dir,
+            database_path,
+            journal_path,
+            storage_type: settings.storage.backend,
+            deny_unknown_fields: false,
+            settings,
+            profile,
+            argument_defaults
+        })
     }
 
     /// Create configuration directories if they don't exist
@@ -63,47 +100,43 @@ impl AppConfig {
         Ok(())
     }
 
-    /// Get the current language setting from config file
-    pub fn get_current_language(&self) -> Result<Language, WorkflowError> {
-        let lang_file = self.config_dir.join("language.txt");
+    /// Change `config.toml` and save it. Only the file is changed: `WF_*` overrides are not
+    /// written back, and the settings of this run stay as they are. Returns the saved settings.
+    pub fn update_settings(
+        &self,
+        change: impl FnOnce(&mut Settings) -> Result<(), WorkflowError>
+    ) -> Result<Settings, WorkflowError> {
+        let mut settings = Settings::load(&self.config_dir)?;
+        change(&mut settings)?;
+        settings.save(&self.config_dir)?;
+        Ok(settings)
+    }
 
-        if lang_file.exists() {
-            let content = fs::read_to_string(&lang_file)
-                .map_err(|e| StorageError::Io(format!("Failed to read language config: {}", e)))?;
-            let lang_code = content.trim();
-            Language::try_from(lang_code)
-        } else {
-            // Default to English if no config file exists
-            Ok(Language::English)
-        }
+    /// Get the current language setting
+    pub fn get_current_language(&self) -> Result<Language, WorkflowError> {
+        Ok(self.settings.ui.language)
     }
 
-    /// Set the current language setting in config file
+    /// Set the language in the config file
     pub fn set_current_language(&self, language: Language) -> Result<(), WorkflowError> {
-        let lang_file = self.config_dir.join("language.txt");
-        fs::write(&lang_file, language.code())
-            .map_err(|e| StorageError::Io(format!("Failed to write language config: {}", e)))?;
-        Ok(())
+        self.update_settings(|settings| {
+            settings.ui.language = language;
+            Ok(())
+        })
+        .map(drop)
     }
 
-    /// Get the current storage backend setting from config file
+    /// Get the current storage backend setting
     pub fn get_current_storage(&self) -> Result<EventStoreType, WorkflowError> {
-        let storage_file = self.config_dir.join("storage.txt");
-
-        if storage_file.exists() {
-            let content = fs::read_to_string(&storage_file)
-                .map_err(|e| StorageError::Io(format!("Failed to read storage config: {}", e)))?;
-            EventStoreType::from_str(content.trim()).map_err(|e| WorkflowError::Config(e))
-        } else {
-            Ok(EventStoreType::InMemory)
-        }
+        Ok(self.settings.storage.backend)
     }
 
-    /// Set the current storage backend setting in config file
+    /// Set the storage backend in the config file
     pub fn set_current_storage(&self, storage_type: EventStoreType) -> Result<(), WorkflowError> {
-        let storage_file = self.config_dir.join("storage.txt");
-        fs::write(&storage_file, storage_type.as_str())
-            .map_err(|e| StorageError::Io(format!("Failed to write storage config: {}", e)))?;
-        Ok(())
+        self.update_settings(|settings| {
+            settings.storage.backend = storage_type;
+            Ok(())
+        })
+        .map(drop)
     }
 }
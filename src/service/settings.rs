@@ -0,0 +1,469 @@
+//! User settings kept in `config.toml`
+//!
+//! Every key can be overridden for a single run by a `WF_*` environment variable named after it,
+//! e.g. `WF_UI_LANGUAGE=es` for `ui.language`, so tests and CI don't depend on the user's file.
+//! The `language.txt` and `storage.txt` files of older versions are folded into `config.toml` the
+//! first time it is missing.
+//!
+//! `[remotes.<name>]` tables are the Git repositories `wf sync` pulls workflows from, and
+//! `[profiles.<name>]` tables describe whole environments (workflow directories, event store,
+//! remotes and argument defaults) that `--profile` or `WF_PROFILE` switch to.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{adapter::storage::EventStoreType, domain::error::WorkflowError, i18n::Language, t_params};
+
+/// Name of the settings file in the configuration directory
+pub const SETTINGS_FILE: &str = "config.toml";
+
+/// Rows shown at once by select prompts unless `ui.page_size` says otherwise
+pub const DEFAULT_PAGE_SIZE: usize = 10;
+
+/// Every settings key, as used by `wf config` and the `WF_*` variables
+pub const SETTINGS_KEYS: [&str; 6] =
+    ["ui.language", "ui.page_size", "storage.backend", "sync.ssh_key", "execution.timeout_secs", "clipboard.confirm"];
+
+/// Contents of `config.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub ui:        UiSettings,
+    pub storage:   StorageSettings,
+    pub sync:      SyncSettings,
+    pub execution: ExecutionSettings,
+    pub clipboard: ClipboardSettings,
+    /// Repositories synced by `wf sync`, by name; managed with `wf remote`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub remotes:   BTreeMap<String, Remote>,
+    /// Named environments, edited in the file rather than through `wf config`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles:  BTreeMap<String, Profile>
+}
+
+/// How the CLI talks to the user
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiSettings {
+    /// Language of messages and prompts
+    pub language:  Language,
+    /// Rows shown at once by select prompts
+    pub page_size: usize
+}
+
+impl Default for UiSettings {
+    fn default() -> Self {
+        Self { language: Language::English, page_size: DEFAULT_PAGE_SIZE }
+    }
+}
+
+/// Where session events are kept
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageSettings {
+    pub backend: EventStoreType
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self { backend: EventStoreType::InMemory }
+    }
+}
+
+/// What `wf sync` does for every remote
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SyncSettings {
+    /// SSH key for the remotes that don't set their own
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh_key: Option<String>
+}
+
+/// A Git repository of workflows, synced into the folder named after it in the workflows
+/// directory so its workflows get the remote's name as namespace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Remote {
+    pub url:     String,
+    #[serde(default = "default_branch")]
+    pub branch:  String,
+    /// SSH key for private repositories
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_key: Option<String>,
+    /// Only take workflows from this folder of the repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path:    Option<String>
+}
+
+fn default_branch() -> String {
+    "main".to_string()
+}
+
+impl Remote {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into(), branch: default_branch(), ssh_key: None, path: None }
+    }
+}
+
+/// Limits on running commands
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExecutionSettings {
+    /// How long a command (prompts included) may take, in seconds; 0 waits forever.
+    /// Running the workflow itself (`--exec`, steps) is never timed out.
+    pub timeout_secs: u64
+}
+
+impl Default for ExecutionSettings {
+    fn default() -> Self {
+        Self { timeout_secs: 300 }
+    }
+}
+
+impl ExecutionSettings {
+    /// The timeout to wait for a command with, none when disabled
+    pub fn timeout(&self) -> Option<Duration> {
+        (self.timeout_secs > 0).then(|| Duration::from_secs(self.timeout_secs))
+    }
+}
+
+/// Copying rendered commands
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClipboardSettings {
+    /// Ask before copying in interactive runs
+    pub confirm: bool
+}
+
+impl Default for ClipboardSettings {
+    fn default() -> Self {
+        Self { confirm: true }
+    }
+}
+
+/// A named environment, so the workflows and history of one client don't mix with another's.
+/// Paths may start with `~/`; relative ones are taken from the configuration directory.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// Workflow directories. The first takes the place of the global directory (and is where
+    /// `wf sync` writes), the others are searched after it. `profiles/<name>/workflows` when empty.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub workflows_dirs: Vec<PathBuf>,
+    /// Directory of the RocksDB event store; `profiles/<name>/rocksdb` when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_path:   Option<PathBuf>,
+    /// Used instead of the top-level remotes while the profile is active
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub remotes:        BTreeMap<String, Remote>,
+    /// Argument defaults by argument name, used over the workflow's own `default_value`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub defaults:       BTreeMap<String, String>
+}
+
+impl Profile {
+    /// Workflow directories of the profile `name`, resolved
+    pub fn workflow_dirs(&self, config_dir: &Path, name: &str) -> Vec<PathBuf> {
+        if self.workflows_dirs.is_empty() {
+            return vec![profile_dir(config_dir, name).join("workflows")];
+        }
+        self.workflows_dirs.iter().map(|dir| resolve_path(config_dir, dir)).collect()
+    }
+
+    /// Event store directory of the profile `name`, resolved
+    pub fn storage_path(&self, config_dir: &Path, name: &str) -> PathBuf {
+        match &self.storage_path {
+            Some(path) => resolve_path(config_dir, path),
+            None => profile_dir(config_dir, name).join("rocksdb")
+        }
+    }
+}
+
+fn profile_dir(config_dir: &Path, name: &str) -> PathBuf {
+    config_dir.join("profiles").join(name)
+}
+
+/// `~/` expanded to the home directory and relative paths joined to `config_dir`
+fn resolve_path(config_dir: &Path, path: &Path) -> PathBuf {
+    if let Ok(rest) = path.strip_prefix("~")
+        && let Some(dirs) = directories::BaseDirs::new()
+    {
+        return dirs.home_dir().join(rest);
+    }
+    config_dir.join(path)
+}
+
+impl Settings {
+    /// Read `config.toml` from `config_dir`, migrating the older `.txt` files when it's missing.
+    /// `WF_*` overrides are not applied; see [`Settings::with_overrides`].
+    pub fn load(config_dir: &Path) -> Result<Self, WorkflowError> {
+        let path = config_dir.join(SETTINGS_FILE);
+        if path.exists() {
+            let contents = fs::read_to_string(&path)
+                .map_err(|e| WorkflowError::Config(t_params!("error_failed_to_read_config", &[&e.to_string()])))?;
+            return Self::parse(&contents);
+        }
+        Self::migrate(config_dir)
+    }
+
+    /// Read settings from TOML, holding them to the same rules as [`Settings::set`]
+    pub fn parse(contents: &str) -> Result<Self, WorkflowError> {
+        let settings: Self = toml::from_str(contents).map_err(|e| {
+            WorkflowError::Config(t_params!("error_invalid_config_file", &[SETTINGS_FILE, e.message()]))
+        })?;
+        if settings.ui.page_size == 0 {
+            return Err(invalid("ui.page_size", "0"));
+        }
+        Ok(settings)
+    }
+
+    /// Write the settings to `config.toml` in `config_dir`
+    pub fn save(&self, config_dir: &Path) -> Result<(), WorkflowError> {
+        let contents = toml::to_string_pretty(self)
+            .map_err(|e| WorkflowError::Config(t_params!("error_failed_to_write_config", &[&e.to_string()])))?;
+        fs::write(config_dir.join(SETTINGS_FILE), contents)
+            .map_err(|e| WorkflowError::Config(t_params!("error_failed_to_write_config", &[&e.to_string()])))
+    }
+
+    /// Build `config.toml` from `language.txt` and `storage.txt`, then remove them. Without
+    /// either file there's nothing to migrate and the defaults are returned without writing.
+    fn migrate(config_dir: &Path) -> Result<Self, WorkflowError> {
+        let mut settings = Self::default();
+        let legacy = [("ui.language", "language.txt"), ("storage.backend", "storage.txt")];
+        let mut migrated = Vec::new();
+
+        for (key, file) in legacy {
+            let path = config_dir.join(file);
+            if let Ok(contents) = fs::read_to_string(&path) {
+                settings.set(key, contents.trim())?;
+                migrated.push(path);
+            }
+        }
+
+        if !migrated.is_empty() {
+            settings.save(config_dir)?;
+            for path in migrated {
+                fs::remove_file(path).ok();
+            }
+        }
+        Ok(settings)
+    }
+
+    /// The profile called `name`
+    pub fn profile(&self, name: &str) -> Result<&Profile, WorkflowError> {
+        self.profiles.get(name).ok_or_else(|| {
+            let available = self.profiles.keys().map(String::as_str).collect::<Vec<_>>().join(", ");
+            WorkflowError::Config(t_params!("error_unknown_profile", &[name, &available]))
+        })
+    }
+
+    /// The remotes `wf remote` edits: the profile's when one is active, the top-level ones
+    /// otherwise
+    pub fn remotes_mut(&mut self, profile: Option<&str>) -> Result<&mut BTreeMap<String, Remote>, WorkflowError> {
+        match profile {
+            Some(name) => {
+                self.profile(name)?;
+                Ok(&mut self.profiles.get_mut(name).expect("profile exists").remotes)
+            }
+            None => Ok(&mut self.remotes)
+        }
+    }
+
+    /// Environment variable overriding `key`, e.g. `WF_UI_PAGE_SIZE` for `ui.page_size`
+    pub fn env_var(key: &str) -> String {
+        format!("WF_{}", key.to_uppercase().replace('.', "_"))
+    }
+
+    /// The settings with every `WF_*` variable that `lookup` finds applied on top
+    pub fn with_overrides(mut self, lookup: impl Fn(&str) -> Option<String>) -> Result<Self, WorkflowError> {
+        for key in SETTINGS_KEYS {
+            if let Some(value) = lookup(&Self::env_var(key)) {
+                self.set(key, &value)?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Current value of `key` as `wf config get` shows it
+    pub fn get(&self, key: &str) -> Result<String, WorkflowError> {
+        Ok(match key {
+            "ui.language" => self.ui.language.code().to_string(),
+            "ui.page_size" => self.ui.page_size.to_string(),
+            "storage.backend" => self.storage.backend.as_str().to_string(),
+            "sync.ssh_key" => self.sync.ssh_key.clone().unwrap_or_default(),
+            "execution.timeout_secs" => self.execution.timeout_secs.to_string(),
+            "clipboard.confirm" => self.clipboard.confirm.to_string(),
+            _ => return Err(unknown_key(key))
+        })
+    }
+
+    /// Parse `value` into `key`, rejecting values the setting can't hold
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), WorkflowError> {
+        match key {
+            "ui.language" => self.ui.language = Language::try_from(value)?,
+            "ui.page_size" => {
+                self.ui.page_size = value.parse().ok().filter(|size| *size > 0).ok_or_else(|| invalid(key, value))?
+            }
+            "storage.backend" => {
+                self.storage.backend = EventStoreType::from_str(value).map_err(WorkflowError::Config)?
+            }
+            "sync.ssh_key" => self.sync.ssh_key = Some(value.to_string()).filter(|key| !key.is_empty()),
+            "execution.timeout_secs" => self.execution.timeout_secs = value.parse().map_err(|_| invalid(key, value))?,
+            "clipboard.confirm" => self.clipboard.confirm = value.parse().map_err(|_| invalid(key, value))?,
+            _ => return Err(unknown_key(key))
+        }
+        Ok(())
+    }
+
+    /// Put `key` back to its default
+    pub fn unset(&mut self, key: &str) -> Result<(), WorkflowError> {
+        self.set(key, &Self::default().get(key)?)
+    }
+}
+
+fn unknown_key(key: &str) -> WorkflowError {
+    WorkflowError::Config(t_params!("error_unknown_config_key", &[key, &SETTINGS_KEYS.join(", ")]))
+}
+
+fn invalid(key: &str, value: &str) -> WorkflowError {
+    WorkflowError::Config(t_params!("error_invalid_config_value", &[value, key]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn every_key_round_trips_through_toml() {
+        let mut settings = Settings::default();
+        settings.set("ui.language", "es").unwrap();
+        settings.set("ui.page_size", "25").unwrap();
+        settings.set("storage.backend", "rocksdb").unwrap();
+        settings.set("sync.ssh_key", "~/.ssh/id_ed25519").unwrap();
+        settings.set("execution.timeout_secs", "0").unwrap();
+        settings.set("clipboard.confirm", "false").unwrap();
+
+        let saved = toml::to_string_pretty(&settings).unwrap();
+        assert!(saved.contains("[ui]\nlanguage = \"es\""));
+        assert_eq!(Settings::parse(&saved).unwrap(), settings);
+        assert_eq!(settings.execution.timeout(), None);
+        for key in SETTINGS_KEYS {
+            assert!(settings.get(key).is_ok());
+        }
+    }
+
+    #[test]
+    fn bad_values_and_keys_are_rejected() {
+        let mut settings = Settings::default();
+        assert!(settings.set("ui.page_size", "0").is_err());
+        assert!(settings.set("ui.language", "xx").is_err());
+        assert!(settings.set("clipboard.confirm", "maybe").is_err());
+        assert!(settings.set("ui.colour", "red").is_err());
+        assert!(Settings::parse("[ui]\ncolour = \"red\"").is_err());
+        assert!(Settings::parse("[ui]\npage_size = 0").is_err());
+    }
+
+    #[test]
+    fn env_overrides_win_over_the_file() {
+        let env: HashMap<&str, &str> = [("WF_UI_PAGE_SIZE", "5"), ("WF_CLIPBOARD_CONFIRM", "false")].into();
+        let settings = Settings::parse("[ui]\npage_size = 20")
+            .unwrap()
+            .with_overrides(|name| env.get(name).map(|value| value.to_string()))
+            .unwrap();
+
+        assert_eq!(settings.ui.page_size, 5);
+        assert!(!settings.clipboard.confirm);
+        assert_eq!(Settings::env_var("execution.timeout_secs"), "WF_EXECUTION_TIMEOUT_SECS");
+    }
+
+    #[test]
+    fn unset_restores_defaults() {
+        let mut settings = Settings::default();
+        settings.set("ui.page_size", "40").unwrap();
+        settings.set("clipboard.confirm", "false").unwrap();
+        settings.set("sync.ssh_key", "~/.ssh/id_rsa").unwrap();
+        settings.unset("ui.page_size").unwrap();
+        settings.unset("sync.ssh_key").unwrap();
+        settings.unset("clipboard.confirm").unwrap();
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn profiles_resolve_their_paths() {
+        let settings = Settings::parse(
+            r#"
+[profiles.acme]
+workflows_dirs = ["clients/acme", "/opt/acme/shared"]
+defaults = { namespace = "acme-prod" }
+
+[profiles.acme.remotes.ops]
+url = "git@example.com:acme/workflows.git"
+
+[profiles.personal]
+"#
+        )
+        .unwrap();
+        let config_dir = Path::new("/config");
+
+        let acme = settings.profile("acme").unwrap();
+        assert_eq!(
+            acme.workflow_dirs(config_dir, "acme"),
+            vec![PathBuf::from("/config/clients/acme"), PathBuf::from("/opt/acme/shared")]
+        );
+        assert_eq!(acme.storage_path(config_dir, "acme"), PathBuf::from("/config/profiles/acme/rocksdb"));
+        assert_eq!(acme.remotes["ops"].branch, "main");
+        assert_eq!(acme.defaults["namespace"], "acme-prod");
+
+        let personal = settings.profile("personal").unwrap();
+        assert_eq!(
+            personal.workflow_dirs(config_dir, "personal"),
+            vec![PathBuf::from("/config/profiles/personal/workflows")]
+        );
+        assert!(settings.profile("globex").is_err());
+        assert_eq!(Settings::parse(&toml::to_string_pretty(&settings).unwrap()).unwrap(), settings);
+    }
+
+    #[test]
+    fn remotes_are_edited_where_the_profile_says() {
+        let mut settings = Settings::parse(
+            r#"
+[remotes.vault]
+url = "https://example.com/vault.git"
+branch = "develop"
+path = "k8s"
+
+[profiles.acme]
+"#
+        )
+        .unwrap();
+        assert_eq!(settings.remotes["vault"].path.as_deref(), Some("k8s"));
+        assert!(Settings::parse("[remotes.vault]\nbranch = \"main\"").is_err());
+
+        settings.remotes_mut(Some("acme")).unwrap().insert("ops".to_string(), Remote::new("git@example.com:ops.git"));
+        assert!(!settings.remotes.contains_key("ops"));
+        assert!(settings.profiles["acme"].remotes.contains_key("ops"));
+        assert_eq!(settings.remotes_mut(None).unwrap().len(), 1);
+        assert!(settings.remotes_mut(Some("globex")).is_err());
+    }
+
+    #[test]
+    fn legacy_text_files_are_migrated() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("language.txt"), "es\n").unwrap();
+        fs::write(dir.path().join("storage.txt"), "rocksdb").unwrap();
+
+        let settings = Settings::load(dir.path()).unwrap();
+        assert_eq!(settings.ui.language, Language::Spanish);
+        assert_eq!(settings.storage.backend, EventStoreType::RocksDb);
+        assert!(!dir.path().join("language.txt").exists());
+        assert_eq!(Settings::load(dir.path()).unwrap(), settings);
+    }
+}